@@ -1,6 +1,12 @@
 use bevy::prelude::*;
 use tect_state::app_state::*;
+use tect_ui::loading_screen::LoadingScreenPlugin;
+use tect_ui::localized_text::LocalizedTextPlugin;
 use tect_ui::main_ui::*;
+use tect_ui::minimap::MinimapPlugin;
+use tect_ui::pause_menu::PauseMenuPlugin;
+use tect_ui::save_slots::SaveSlotsPlugin;
+use tect_ui::settings_menu::SettingsMenuPlugin;
 use tect_world::world_map::WorldScenePlugin;
 
 pub fn run() {
@@ -9,5 +15,11 @@ pub fn run() {
         .add_plugins(WorldScenePlugin)
         .add_plugins(GameStatePlugin)
         .add_plugins(MainUiPlugin)
+        .add_plugins(LoadingScreenPlugin)
+        .add_plugins(MinimapPlugin)
+        .add_plugins(PauseMenuPlugin)
+        .add_plugins(SettingsMenuPlugin)
+        .add_plugins(SaveSlotsPlugin)
+        .add_plugins(LocalizedTextPlugin)
         .run();
 }
@@ -0,0 +1,54 @@
+///相机书签：Ctrl + F1..F8 记录当前视角，F1..F8 平滑跳回，方便在多个基地之间来回切换
+///按键来自 InputMap（书签动作与记录书签修饰键均可改键），书签随存档保存，新游戏从空书签开始
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use tect_state::input::{ActionState, InputAction};
+
+use crate::follow::CameraFollow;
+use crate::god_view_camera::{CameraPose, GodViewCamera};
+
+pub const BOOKMARK_SLOTS: usize = InputAction::BOOKMARKS.len();
+
+/// 当前地图的相机书签
+#[derive(Resource, Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CameraBookmarks {
+    pub slots: [Option<CameraPose>; BOOKMARK_SLOTS],
+}
+
+impl CameraBookmarks {
+    pub fn get(&self, slot: usize) -> Option<CameraPose> {
+        self.slots.get(slot).copied().flatten()
+    }
+
+    pub fn set(&mut self, slot: usize, pose: CameraPose) {
+        if let Some(entry) = self.slots.get_mut(slot) {
+            *entry = Some(pose);
+        }
+    }
+}
+
+/// 记录与跳转书签
+pub(crate) fn camera_bookmark_hotkeys(
+    actions: Res<ActionState>,
+    mut bookmarks: ResMut<CameraBookmarks>,
+    mut follow: ResMut<CameraFollow>,
+    mut camera_query: Query<&mut GodViewCamera>,
+) {
+    let Some(slot) = InputAction::BOOKMARKS
+        .iter()
+        .position(|action| actions.just_pressed(*action))
+    else {
+        return;
+    };
+    let Ok(mut camera) = camera_query.single_mut() else {
+        return;
+    };
+
+    if actions.pressed(InputAction::StoreBookmark) {
+        bookmarks.set(slot, camera.target());
+    } else if let Some(pose) = bookmarks.get(slot) {
+        // 只改目标姿态，由 update_camera_transform 平滑过渡
+        follow.release();
+        camera.set_target(pose);
+    }
+}
@@ -0,0 +1,390 @@
+///过场镜头：按 RON 定义的关键帧轨迹驱动 GodViewCamera，用于开场、建筑展示等脚本化镜头
+///关键帧之间用 Catmull-Rom 样条插值，每段可指定缓动函数；播放期间屏蔽玩家输入（暂停键除外，暂停时轨迹随 Time<Virtual> 停下），结束后交还控制
+///轨迹文件后缀为 .camera.ron，例如：
+///(
+///    keyframes: [
+///        (time: 0.0, focus: (0.0, 0.0, 0.0), distance: 40.0, yaw: 0.0, pitch: -1.2),
+///        (time: 4.0, focus: (10.0, 0.0, 5.0), distance: 15.0, yaw: 1.0, pitch: -0.6, easing: CubicInOut),
+///    ],
+///    restore_on_finish: true,
+///)
+use bevy::{
+    asset::{io::Reader, AssetLoader, LoadContext},
+    math::curve::{Curve, EaseFunction},
+    prelude::*,
+    window::{CursorGrabMode, CursorOptions, PrimaryWindow},
+};
+use serde::{Deserialize, Serialize};
+use std::f32::consts::{PI, TAU};
+use std::ops::{Add, Mul, Sub};
+use tect_state::app_state::*;
+use tect_state::input::ActionState;
+
+use crate::follow::CameraFollow;
+use crate::god_view_camera::{CameraPose, GodViewCamera};
+
+// ──────────────────────────────────────────────────────────────
+// 1. 轨迹资源与加载器
+// ──────────────────────────────────────────────────────────────
+
+/// 轨迹上的一个关键帧
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct CameraKeyframe {
+    /// 到达该关键帧的时间（秒，从轨迹开始计）
+    pub time: f32,
+    pub focus: Vec3,
+    pub distance: f32,
+    pub yaw: f32,
+    pub pitch: f32,
+    /// 从上一关键帧过渡到本关键帧所用的缓动
+    #[serde(default = "default_easing")]
+    pub easing: EaseFunction,
+}
+
+fn default_easing() -> EaseFunction {
+    EaseFunction::Linear
+}
+
+impl CameraKeyframe {
+    fn pose(&self) -> CameraPose {
+        CameraPose {
+            focus: self.focus,
+            distance: self.distance,
+            yaw: self.yaw,
+            pitch: self.pitch,
+        }
+    }
+}
+
+/// 相机轨迹
+#[derive(Asset, TypePath, Debug, Clone, Serialize, Deserialize)]
+pub struct CameraTrack {
+    /// 按时间排序的关键帧
+    pub keyframes: Vec<CameraKeyframe>,
+    /// 播放结束后平滑回到播放前的姿态，否则停在最后一帧
+    #[serde(default)]
+    pub restore_on_finish: bool,
+}
+
+impl CameraTrack {
+    /// 轨迹总时长
+    pub fn duration(&self) -> f32 {
+        self.keyframes.last().map_or(0.0, |key| key.time)
+    }
+
+    /// 采样 `time` 秒时的相机姿态
+    pub fn sample(&self, time: f32) -> Option<CameraPose> {
+        let keys = &self.keyframes;
+        let first = keys.first()?;
+        if keys.len() == 1 || time <= first.time {
+            return Some(first.pose());
+        }
+
+        // 所在的段 [index, index + 1]，超出末尾时停在最后一段的终点
+        let index = keys
+            .windows(2)
+            .position(|pair| time < pair[1].time)
+            .unwrap_or(keys.len() - 2);
+        let (p0, p1, p2) = (
+            &keys[index.saturating_sub(1)],
+            &keys[index],
+            &keys[index + 1],
+        );
+        let p3 = keys.get(index + 2).unwrap_or(p2);
+
+        let span = (p2.time - p1.time).max(f32::EPSILON);
+        let u = p2
+            .easing
+            .sample_clamped(((time - p1.time) / span).clamp(0.0, 1.0));
+
+        // 偏航角先展开成连续值，避免跨越 ±π 时绕远路
+        let yaw1 = p1.yaw;
+        let yaw0 = yaw1 + wrap_angle(p0.yaw - yaw1);
+        let yaw2 = yaw1 + wrap_angle(p2.yaw - yaw1);
+        let yaw3 = yaw2 + wrap_angle(p3.yaw - yaw2);
+
+        Some(CameraPose {
+            focus: catmull_rom(p0.focus, p1.focus, p2.focus, p3.focus, u),
+            distance: catmull_rom(p0.distance, p1.distance, p2.distance, p3.distance, u).max(0.0),
+            yaw: catmull_rom(yaw0, yaw1, yaw2, yaw3, u),
+            pitch: catmull_rom(p0.pitch, p1.pitch, p2.pitch, p3.pitch, u),
+        })
+    }
+}
+
+fn wrap_angle(angle: f32) -> f32 {
+    (angle + PI).rem_euclid(TAU) - PI
+}
+
+/// 均匀 Catmull-Rom 样条，在 p1（t = 0）与 p2（t = 1）之间插值
+fn catmull_rom<T>(p0: T, p1: T, p2: T, p3: T, t: f32) -> T
+where
+    T: Copy + Add<Output = T> + Sub<Output = T> + Mul<f32, Output = T>,
+{
+    let t2 = t * t;
+    let t3 = t2 * t;
+    (p1 * 2.0
+        + (p2 - p0) * t
+        + (p0 * 2.0 - p1 * 5.0 + p2 * 4.0 - p3) * t2
+        + (p1 * 3.0 - p0 - p2 * 3.0 + p3) * t3)
+        * 0.5
+}
+
+#[derive(Default, TypePath)]
+struct CameraTrackLoader;
+
+impl AssetLoader for CameraTrackLoader {
+    type Asset = CameraTrack;
+    type Settings = ();
+    type Error = BevyError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<CameraTrack, BevyError> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let mut track: CameraTrack = ron::de::from_bytes(&bytes)?;
+        track.keyframes.sort_by(|a, b| a.time.total_cmp(&b.time));
+        Ok(track)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["camera.ron"]
+    }
+}
+
+// ──────────────────────────────────────────────────────────────
+// 2. 播放控制
+// ──────────────────────────────────────────────────────────────
+
+/// 开始播放轨迹，正在播放的轨迹会被替换
+#[derive(Message, Debug, Clone)]
+pub struct PlayCameraTrack(pub Handle<CameraTrack>);
+
+/// 提前结束当前轨迹
+#[derive(Message, Debug, Clone, Copy)]
+pub struct StopCameraTrack;
+
+/// 当前播放状态
+#[derive(Resource, Debug, Default)]
+pub struct CinematicPlayback {
+    active: Option<ActiveTrack>,
+}
+
+#[derive(Debug)]
+struct ActiveTrack {
+    track: Handle<CameraTrack>,
+    elapsed: f32,
+    /// 播放前的目标姿态，用于结束后恢复
+    previous: CameraPose,
+}
+
+impl CinematicPlayback {
+    pub fn is_playing(&self) -> bool {
+        self.active.is_some()
+    }
+}
+
+/// 运行条件：是否正在播放过场镜头
+pub fn cinematic_playing(playback: Res<CinematicPlayback>) -> bool {
+    playback.is_playing()
+}
+
+pub struct CinematicCameraPlugin;
+
+impl Plugin for CinematicCameraPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<CameraTrack>()
+            .init_asset_loader::<CameraTrackLoader>()
+            .init_resource::<CinematicPlayback>()
+            .add_message::<PlayCameraTrack>()
+            .add_message::<StopCameraTrack>()
+            .add_systems(OnExit(AppState::InGame), stop_cinematic_on_exit);
+    }
+}
+
+// ──────────────────────────────────────────────────────────────
+// 3. 每帧推进轨迹（在 GodViewCameraPlugin 的系统链中运行）
+// ──────────────────────────────────────────────────────────────
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn play_camera_tracks(
+    time: Res<Time>,
+    asset_server: Res<AssetServer>,
+    tracks: Res<Assets<CameraTrack>>,
+    mut play: MessageReader<PlayCameraTrack>,
+    mut stop: MessageReader<StopCameraTrack>,
+    mut playback: ResMut<CinematicPlayback>,
+    mut actions: ResMut<ActionState>,
+    mut follow: ResMut<CameraFollow>,
+    mut cursor: Single<&mut CursorOptions, With<PrimaryWindow>>,
+    mut camera_query: Query<&mut GodViewCamera>,
+) {
+    let Ok(mut camera) = camera_query.single_mut() else {
+        return;
+    };
+
+    if let Some(PlayCameraTrack(track)) = play.read().last() {
+        let previous = match &playback.active {
+            // 替换正在播放的轨迹时保留最初的姿态
+            Some(active) => active.previous,
+            None => camera.target(),
+        };
+        playback.active = Some(ActiveTrack {
+            track: track.clone(),
+            elapsed: 0.0,
+            previous,
+        });
+        // 接管相机：挂起玩家输入，中断跟随与进行中的右键拖拽
+        actions.set_suspended(true);
+        follow.release();
+        cursor.grab_mode = CursorGrabMode::None;
+        cursor.visible = true;
+    }
+    let stop_requested = stop.read().count() > 0;
+
+    let Some(active) = playback.active.as_mut() else {
+        return;
+    };
+
+    let finished = match tracks.get(&active.track) {
+        Some(track) => {
+            active.elapsed += time.delta_secs();
+            if let Some(pose) = track.sample(active.elapsed) {
+                camera.set_target(pose);
+                camera.snap();
+            }
+            let done = stop_requested || active.elapsed >= track.duration();
+            if done && track.restore_on_finish {
+                // 不 snap，由平滑过渡回原来的视角
+                camera.set_target(active.previous);
+            }
+            done
+        }
+        None => {
+            // 轨迹仍在加载时原地等待；加载失败则直接结束，避免输入一直被挂起
+            let failed = asset_server
+                .get_load_state(&active.track)
+                .is_some_and(|state| state.is_failed());
+            if failed {
+                warn!("相机轨迹加载失败: {:?}", active.track.path());
+            }
+            stop_requested || failed
+        }
+    };
+
+    if finished {
+        playback.active = None;
+        actions.set_suspended(false);
+    }
+}
+
+/// 离开游戏时中止播放并交还输入
+fn stop_cinematic_on_exit(
+    mut playback: ResMut<CinematicPlayback>,
+    mut actions: ResMut<ActionState>,
+) {
+    if playback.active.take().is_some() {
+        actions.set_suspended(false);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(time: f32, x: f32, yaw: f32, easing: EaseFunction) -> CameraKeyframe {
+        CameraKeyframe {
+            time,
+            focus: Vec3::new(x, 0.0, 0.0),
+            distance: 20.0,
+            yaw,
+            pitch: -0.8,
+            easing,
+        }
+    }
+
+    fn track(keyframes: Vec<CameraKeyframe>) -> CameraTrack {
+        CameraTrack {
+            keyframes,
+            restore_on_finish: false,
+        }
+    }
+
+    fn focus_x(track: &CameraTrack, time: f32) -> f32 {
+        track.sample(time).unwrap().focus.x
+    }
+
+    #[test]
+    fn empty_track_has_no_pose() {
+        let track = track(Vec::new());
+        assert_eq!(track.duration(), 0.0);
+        assert!(track.sample(0.0).is_none());
+    }
+
+    #[test]
+    fn single_keyframe_holds_its_pose() {
+        let track = track(vec![key(1.0, 5.0, 0.3, EaseFunction::Linear)]);
+        assert_eq!(track.duration(), 1.0);
+        for time in [0.0, 1.0, 10.0] {
+            assert_eq!(track.sample(time), Some(track.keyframes[0].pose()));
+        }
+    }
+
+    #[test]
+    fn sample_hits_keyframes_and_clamps_outside_the_track() {
+        let track = track(vec![
+            key(0.0, 0.0, 0.0, EaseFunction::Linear),
+            key(2.0, 10.0, 0.5, EaseFunction::Linear),
+            key(5.0, 4.0, 1.0, EaseFunction::Linear),
+        ]);
+        assert_eq!(track.duration(), 5.0);
+        for keyframe in &track.keyframes {
+            let pose = track.sample(keyframe.time).unwrap();
+            assert!(pose.focus.abs_diff_eq(keyframe.focus, 1e-4), "{pose:?}");
+            assert!((pose.yaw - keyframe.yaw).abs() < 1e-4, "{pose:?}");
+        }
+        assert_eq!(track.sample(-1.0), Some(track.keyframes[0].pose()));
+        let last = track.keyframes[2].pose();
+        for time in [5.0, 6.0, 100.0] {
+            let pose = track.sample(time).unwrap();
+            assert!(pose.focus.abs_diff_eq(last.focus, 1e-4), "{pose:?}");
+        }
+    }
+
+    #[test]
+    fn two_linear_keyframes_meet_halfway() {
+        let track = track(vec![
+            key(0.0, 0.0, 0.0, EaseFunction::Linear),
+            key(4.0, 8.0, 0.0, EaseFunction::Linear),
+        ]);
+        assert!((focus_x(&track, 2.0) - 4.0).abs() < 1e-4);
+        assert!(focus_x(&track, 1.0) < focus_x(&track, 3.0));
+    }
+
+    #[test]
+    fn easing_of_the_target_keyframe_shapes_the_segment() {
+        let linear = track(vec![
+            key(0.0, 0.0, 0.0, EaseFunction::Linear),
+            key(4.0, 8.0, 0.0, EaseFunction::Linear),
+        ]);
+        let eased = track(vec![
+            key(0.0, 0.0, 0.0, EaseFunction::Linear),
+            key(4.0, 8.0, 0.0, EaseFunction::CubicIn),
+        ]);
+        assert!(focus_x(&eased, 2.0) < focus_x(&linear, 2.0));
+        assert!((focus_x(&eased, 4.0) - 8.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn yaw_takes_the_short_way_across_pi() {
+        let track = track(vec![
+            key(0.0, 0.0, 3.0, EaseFunction::Linear),
+            key(2.0, 0.0, -3.0, EaseFunction::Linear),
+        ]);
+        let yaw = track.sample(1.0).unwrap().yaw;
+        assert!((wrap_angle(yaw).abs() - PI).abs() < 1e-3, "{yaw}");
+    }
+}
@@ -0,0 +1,103 @@
+///相机跟随：按跟随键（默认 F）开关对选中单位的跟随，焦点以临界阻尼弹簧追上目标；玩家手动平移视角时自动解除
+///按对准键（默认空格）把焦点立即移到选中单位的中心
+use bevy::prelude::*;
+use tect_control::selection::Selected;
+use tect_state::input::{ActionState, InputAction};
+
+use crate::god_view_camera::GodViewCamera;
+
+/// 相机跟随状态
+#[derive(Resource, Debug)]
+pub struct CameraFollow {
+    /// 正在跟随的实体
+    pub target: Option<Entity>,
+    /// 弹簧角频率，越大跟得越紧
+    pub stiffness: f32,
+    velocity: Vec3,
+}
+
+impl Default for CameraFollow {
+    fn default() -> Self {
+        Self {
+            target: None,
+            stiffness: 6.0,
+            velocity: Vec3::ZERO,
+        }
+    }
+}
+
+impl CameraFollow {
+    pub fn follow(&mut self, target: Entity) {
+        self.target = Some(target);
+        self.velocity = Vec3::ZERO;
+    }
+
+    pub fn release(&mut self) {
+        self.target = None;
+        self.velocity = Vec3::ZERO;
+    }
+
+    pub fn is_following(&self) -> bool {
+        self.target.is_some()
+    }
+}
+
+/// 跟随开关与对准选中单位
+pub(crate) fn camera_follow_hotkeys(
+    actions: Res<ActionState>,
+    mut follow: ResMut<CameraFollow>,
+    selected: Query<(Entity, &GlobalTransform), With<Selected>>,
+    mut camera_query: Query<&mut GodViewCamera>,
+) {
+    if actions.just_pressed(InputAction::ToggleFollow) {
+        if follow.is_following() {
+            follow.release();
+        } else if let Some(entity) = selected.iter().map(|(entity, _)| entity).min() {
+            follow.follow(entity);
+        }
+    }
+
+    if actions.just_pressed(InputAction::CenterOnSelection) {
+        let count = selected.iter().len();
+        let Ok(mut camera) = camera_query.single_mut() else {
+            return;
+        };
+        if count > 0 {
+            let center = selected
+                .iter()
+                .map(|(_, transform)| transform.translation())
+                .sum::<Vec3>()
+                / count as f32;
+            camera.focus = center.with_y(camera.focus.y);
+        }
+    }
+}
+
+/// 焦点以临界阻尼弹簧追随目标，与帧率无关
+pub(crate) fn camera_follow_target(
+    time: Res<Time>,
+    mut follow: ResMut<CameraFollow>,
+    targets: Query<&GlobalTransform>,
+    mut camera_query: Query<&mut GodViewCamera>,
+) {
+    let Some(target) = follow.target else {
+        return;
+    };
+    // 目标被销毁时自动解除跟随
+    let Ok(target_transform) = targets.get(target) else {
+        follow.release();
+        return;
+    };
+    let Ok(mut camera) = camera_query.single_mut() else {
+        return;
+    };
+
+    let goal = target_transform.translation().with_y(camera.focus.y);
+    let omega = follow.stiffness;
+    let dt = time.delta_secs();
+    let offset = camera.focus - goal;
+    let decay = (-omega * dt).exp();
+    let temp = (follow.velocity + offset * omega) * dt;
+    follow.velocity = (follow.velocity - temp * omega) * decay;
+    camera.focus = goal + (offset + temp) * decay;
+}
@@ -0,0 +1,160 @@
+///自由飞行调试相机：按自由相机键（默认 F9）开关，期间暂停上帝视角的所有相机系统，用于检查大地图与关卡细节
+///平移键（WASD）前后左右飞行，Q / E 下降 / 上升，鼠标转动视角，滚轮调节飞行速度；关闭后相机回到原来的环绕视角
+use bevy::{
+    input::mouse::AccumulatedMouseMotion,
+    prelude::*,
+    window::{CursorGrabMode, CursorOptions, PrimaryWindow},
+};
+use std::f32::consts::FRAC_PI_2;
+use tect_state::app_state::*;
+use tect_state::input::{ActionState, InputAction};
+
+use crate::cinematic::{play_camera_tracks, PlayCameraTrack};
+use crate::god_view_camera::{fast_multiplier, GodViewCamera};
+use crate::settings::CameraSettings;
+
+pub struct FreeFlyCameraPlugin;
+
+impl Plugin for FreeFlyCameraPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<FreeFlyCamera>()
+            .add_systems(
+                Update,
+                (toggle_free_fly, free_fly_camera.run_if(free_fly_active))
+                    .run_if(in_state(InGameState::Playing))
+                    // 先于轨迹播放退出自由飞行，同一帧内即可开始播放
+                    .before(play_camera_tracks)
+                    .chain(),
+            )
+            .add_systems(OnEnter(InGameState::Paused), exit_free_fly)
+            .add_systems(OnExit(AppState::InGame), exit_free_fly);
+    }
+}
+
+// 俯仰角限制，避免越过正上 / 正下方时视角翻转
+const MAX_PITCH: f32 = FRAC_PI_2 - 0.01;
+// 滚轮每格调整速度的倍率
+const SPEED_STEP: f32 = 1.2;
+
+/// 自由飞行相机状态
+#[derive(Resource, Debug, Clone)]
+pub struct FreeFlyCamera {
+    active: bool,
+    /// 飞行速度（米/秒）
+    pub speed: f32,
+    pub min_speed: f32,
+    pub max_speed: f32,
+    /// 鼠标转动灵敏度（弧度/像素）
+    pub sensitivity: f32,
+    yaw: f32,
+    pitch: f32,
+}
+
+impl Default for FreeFlyCamera {
+    fn default() -> Self {
+        Self {
+            active: false,
+            speed: 20.0,
+            min_speed: 1.0,
+            max_speed: 500.0,
+            sensitivity: 0.003,
+            yaw: 0.0,
+            pitch: 0.0,
+        }
+    }
+}
+
+impl FreeFlyCamera {
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+}
+
+/// 运行条件：是否处于自由飞行模式
+pub fn free_fly_active(free_fly: Res<FreeFlyCamera>) -> bool {
+    free_fly.active
+}
+
+fn toggle_free_fly(
+    actions: Res<ActionState>,
+    mut play: MessageReader<PlayCameraTrack>,
+    mut free_fly: ResMut<FreeFlyCamera>,
+    mut cursor: Single<&mut CursorOptions, With<PrimaryWindow>>,
+    camera_query: Query<&Transform, With<GodViewCamera>>,
+) {
+    // 收到过场镜头时自动退出，交给轨迹控制相机（轨迹播放系统在自由飞行期间不运行，需在这里监听）
+    let cinematic_started = play.read().count() > 0 && free_fly.active;
+    if !actions.just_pressed(InputAction::ToggleFreeCamera) && !cinematic_started {
+        return;
+    }
+    let Ok(transform) = camera_query.single() else {
+        return;
+    };
+
+    free_fly.active = !free_fly.active;
+    if free_fly.active {
+        // 从当前画面开始飞行
+        let (yaw, pitch, _) = transform.rotation.to_euler(EulerRot::YXZ);
+        free_fly.yaw = yaw;
+        free_fly.pitch = pitch;
+        cursor.grab_mode = CursorGrabMode::Locked;
+        cursor.visible = false;
+    } else {
+        // GodViewCamera 的状态期间没有被改动，update_camera_transform 恢复运行后立即回到原来的视角
+        cursor.grab_mode = CursorGrabMode::None;
+        cursor.visible = true;
+    }
+}
+
+fn free_fly_camera(
+    time: Res<Time>,
+    actions: Res<ActionState>,
+    settings: Res<CameraSettings>,
+    mut free_fly: ResMut<FreeFlyCamera>,
+    motion: Res<AccumulatedMouseMotion>,
+    mut camera_query: Query<&mut Transform, With<GodViewCamera>>,
+) {
+    let Ok(mut transform) = camera_query.single_mut() else {
+        return;
+    };
+
+    // 滚轮调节速度
+    let scroll = actions.impulse(InputAction::ZoomIn) - actions.impulse(InputAction::ZoomOut);
+    if scroll != 0.0 {
+        free_fly.speed = (free_fly.speed * SPEED_STEP.powf(scroll))
+            .clamp(free_fly.min_speed, free_fly.max_speed);
+    }
+
+    // 鼠标转动视角
+    let delta = motion.delta;
+    free_fly.yaw -= delta.x * free_fly.sensitivity;
+    free_fly.pitch = (free_fly.pitch - delta.y * free_fly.sensitivity).clamp(-MAX_PITCH, MAX_PITCH);
+    transform.rotation = Quat::from_euler(EulerRot::YXZ, free_fly.yaw, free_fly.pitch, 0.0);
+
+    // 沿视线方向飞行，升降沿世界 Y 轴
+    let input = Vec3::new(
+        actions.axis(InputAction::PanLeft, InputAction::PanRight),
+        actions.axis(InputAction::RotateLeft, InputAction::RotateRight),
+        actions.axis(InputAction::PanBack, InputAction::PanForward),
+    );
+    if input == Vec3::ZERO {
+        return;
+    }
+    let direction = transform.right() * input.x + Vec3::Y * input.y + transform.forward() * input.z;
+    transform.translation += direction.clamp_length_max(1.0)
+        * free_fly.speed
+        * fast_multiplier(&actions, &settings)
+        * time.delta_secs();
+}
+
+/// 暂停或离开游戏时退出自由飞行并释放光标
+fn exit_free_fly(
+    mut free_fly: ResMut<FreeFlyCamera>,
+    mut cursor: Single<&mut CursorOptions, With<PrimaryWindow>>,
+) {
+    if free_fly.active {
+        free_fly.active = false;
+        cursor.grab_mode = CursorGrabMode::None;
+        cursor.visible = true;
+    }
+}
@@ -1,15 +1,31 @@
 use bevy::{
-    input::mouse::{MouseMotion, MouseWheel},
+    input::mouse::AccumulatedMouseMotion,
     prelude::*,
-    time::Stopwatch,
     window::{CursorGrabMode, CursorOptions, PrimaryWindow},
 };
+use serde::{Deserialize, Serialize};
+use std::f32::consts::{PI, TAU};
+use tect_control::gesture::{GestureDrag, GestureDragEnd, GestureDragStart, Gestures};
+use tect_control::raycast::{GroundRaycast, MainCamera};
 use tect_state::app_state::*;
+use tect_state::input::{ActionState, InputAction, InputMap};
+use tect_state::settings::GameSettings;
+
+use crate::bookmarks::{camera_bookmark_hotkeys, CameraBookmarks};
+use crate::cinematic::{cinematic_playing, play_camera_tracks, CinematicCameraPlugin};
+use crate::follow::{camera_follow_hotkeys, camera_follow_target, CameraFollow};
+use crate::free_fly::{free_fly_active, FreeFlyCameraPlugin};
+use crate::settings::{
+    apply_control_settings, camera_bounds_from_ground, load_camera_settings, CameraBounds,
+    CameraSettings,
+};
 
 // --- 1. 组件、资源和常量定义 ---
 
 /// 标记主 3D 相机并存储其控制状态
+/// focus / distance / yaw / pitch 是输入系统写入的目标值，`current` 是实际渲染用的值，每帧平滑地逼近目标
 #[derive(Component)]
+#[require(MainCamera)]
 pub struct GodViewCamera {
     /// 相机环绕或聚焦的中心点 (XZ平面)
     pub focus: Vec3,
@@ -19,39 +35,100 @@ pub struct GodViewCamera {
     pub default_pitch: f32,
     /// 临时旋转模式下的鼠标拖拽灵敏度
     pub sensitivity: f32,
+    /// 偏航角（绕 Y 轴）
+    pub yaw: f32,
+    /// 俯仰角（绕 X 轴）
+    pub pitch: f32,
+    /// 平滑速度（1/秒），越大越跟手，0 表示不平滑
+    pub smoothing: f32,
+    /// 缩放时保持鼠标下的地面点不动
+    pub zoom_to_cursor: bool,
+    /// 当前实际使用的姿态
+    pub current: CameraPose,
 }
 
 impl Default for GodViewCamera {
     fn default() -> Self {
+        // 使用弧度：-45度俯视
+        let default_pitch = -std::f32::consts::FRAC_PI_4;
         Self {
             focus: Vec3::ZERO,
             distance: 25.0,
-            // 使用弧度：-45度俯视
-            default_pitch: -std::f32::consts::FRAC_PI_4,
+            default_pitch,
             sensitivity: 0.005,
+            yaw: 0.0,
+            pitch: default_pitch,
+            smoothing: 12.0,
+            zoom_to_cursor: true,
+            current: CameraPose {
+                focus: Vec3::ZERO,
+                distance: 25.0,
+                yaw: 0.0,
+                pitch: default_pitch,
+            },
+        }
+    }
+}
+
+impl GodViewCamera {
+    /// 目标姿态
+    pub fn target(&self) -> CameraPose {
+        CameraPose {
+            focus: self.focus,
+            distance: self.distance,
+            yaw: self.yaw,
+            pitch: self.pitch,
         }
     }
+
+    /// 设置目标姿态
+    pub fn set_target(&mut self, pose: CameraPose) {
+        self.focus = pose.focus;
+        self.distance = pose.distance;
+        self.yaw = pose.yaw;
+        self.pitch = pose.pitch;
+    }
+
+    /// 跳过平滑，立即到达目标姿态
+    pub fn snap(&mut self) {
+        self.current = self.target();
+    }
+}
+
+/// 相机姿态：焦点、距离与朝向
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct CameraPose {
+    pub focus: Vec3,
+    pub distance: f32,
+    pub yaw: f32,
+    pub pitch: f32,
 }
 
-/// 存储右键拖动时的临时旋转状态
-/// 必须是 Resource 或 Local，这里使用 Local 状态，因为它只在 `camera_right_drag_rotate` 系统中使用。
-#[derive(Default, Resource)]
-struct CameraRotateState {
-    /// 旋转模式下的 Yaw 角（绕 Y 轴）
-    yaw: f32,
-    /// 旋转模式下的 Pitch 角（绕 X 轴）
-    pitch: f32,
-    pub last_manual_pitch: f32, // 记住玩家最后手动设置的 pitch
-    pub has_ever_dragged: bool, // 标记是否曾经拖动过
+impl CameraPose {
+    pub fn rotation(&self) -> Quat {
+        Quat::from_euler(EulerRot::YXZ, self.yaw, self.pitch, 0.0)
+    }
+
+    /// 相机在世界中的位置
+    pub fn translation(&self) -> Vec3 {
+        self.focus + self.rotation() * Vec3::new(0.0, 0.0, self.distance)
+    }
+
+    /// 以 `t`（0~1）向 `target` 插值，偏航角走最短的方向
+    pub fn lerp(&self, target: &CameraPose, t: f32) -> CameraPose {
+        let yaw_delta = (target.yaw - self.yaw + PI).rem_euclid(TAU) - PI;
+        CameraPose {
+            focus: self.focus.lerp(target.focus, t),
+            distance: self.distance.lerp(target.distance, t),
+            yaw: self.yaw + yaw_delta * t,
+            pitch: self.pitch.lerp(target.pitch, t),
+        }
+    }
 }
 
-const EDGE_PAN_THRESHOLD: f32 = 0.005; // 窗口边缘 0.05% 触发平移
-const PAN_SPEED: f32 = 5.0; // 相机平移速度
-const ZOOM_SPEED: f32 = 1.0; // 滚轮缩放速度
-                             // 拖动判定阈值（例如 200 毫秒）
-const DRAG_THRESHOLD_TIME: f32 = 0.3;
-// 鼠标最小移动距离阈值（防止微小抖动触发）
-const DRAG_THRESHOLD_DISTANCE: f32 = 5.0; // 5 像素的移动
+const PAN_REFERENCE_DISTANCE: f32 = 25.0; // 平移速度以该相机距离为基准，拉远时等比加快
+const COLLISION_PROBE_START: f32 = 0.5; // 遮挡检测射线从焦点沿视线偏移该距离后开始，避免命中焦点所在的地面
+const COLLISION_MIN_DISTANCE: f32 = 1.0; // 被遮挡时相机离焦点的最近距离
 
 // --- 2. 插件定义 ---
 
@@ -59,82 +136,105 @@ pub struct GodViewCameraPlugin;
 
 impl Plugin for GodViewCameraPlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<CameraRotateState>() // 注册旋转状态资源
+        app.add_plugins((CinematicCameraPlugin, FreeFlyCameraPlugin))
+            .init_resource::<CameraFollow>()
+            .init_resource::<CameraBookmarks>()
+            .init_resource::<CameraBounds>()
+            .add_observer(camera_bounds_from_ground)
+            .add_systems(Startup, load_camera_settings)
+            .add_systems(OnEnter(InGameState::Paused), cancel_camera_drag)
+            .add_systems(OnExit(AppState::InGame), reset_camera_state)
+            .add_systems(Update, apply_control_settings)
             // .add_systems(Startup, spawn_camera)
             .add_systems(
                 Update,
                 (
-                    camera_zoom,
-                    camera_edge_pan,
-                    camera_right_drag_rotate,
+                    (
+                        camera_zoom,
+                        camera_follow_hotkeys,
+                        camera_edge_pan,
+                        // 手动平移之后再跟随，平移会解除跟随
+                        camera_follow_target,
+                        camera_drag_rotate,
+                        camera_keyboard_rotate,
+                        camera_bookmark_hotkeys,
+                        clamp_camera_target,
+                    )
+                        .chain()
+                        .run_if(not(cinematic_playing)), // 过场镜头播放期间由轨迹接管
+                    play_camera_tracks,
                     // 必须在输入处理之后运行，以应用最终的 Transform
                     update_camera_transform,
+                    avoid_camera_collision,
                 )
-                    .run_if(in_state(AppState::InGame))
+                    .run_if(in_state(InGameState::Playing)) // 暂停时不响应相机输入
+                    .run_if(not(free_fly_active)) // 自由飞行期间暂停上帝视角
                     .chain(), // 链式执行确保顺序
             );
     }
 }
 
-// --- 3. Startup 系统：创建相机 ---
-
-fn spawn_camera(mut commands: Commands) {
-    let camera_data = GodViewCamera::default();
-
-    // 初始化时，根据默认 Yaw 和 Pitch 计算 Transform
-    let rotation = calculate_rotation(0.0, camera_data.default_pitch);
-    let translation = camera_data.focus + rotation * Vec3::new(0.0, 0.0, camera_data.distance);
-
-    commands.spawn((
-        Camera3d::default(),
-        Transform {
-            translation,
-            rotation,
-            ..default()
-        },
-        camera_data,
-    ));
-}
-
 // --- 4. Update 系统：滚轮缩放 ---
 
 fn camera_zoom(
-    mut scroll_events: MessageReader<MouseWheel>,
-    mut camera_query: Query<&mut GodViewCamera>,
+    actions: Res<ActionState>,
+    settings: Res<CameraSettings>,
+    time: Res<Time>,
+    mut camera_query: Query<(&mut GodViewCamera, &Camera, &GlobalTransform)>,
+    window: Single<&Window, With<PrimaryWindow>>,
+    mut ground_raycast: GroundRaycast,
 ) {
-    let mut camera = match camera_query.single_mut() {
+    let (mut camera, render_camera, camera_transform) = match camera_query.single_mut() {
         Ok(c) => c,
         Err(_) => return,
     };
 
-    // `MouseWheel::y` 是滚动的量，正值通常是向上滚动（放大）
-    let scroll_y = scroll_events.read().map(|e| e.y).sum::<f32>();
+    // 滚轮是一次性输入，正值为拉近；按键 / 扳机按住时持续缩放
+    let scroll_y = actions.impulse(InputAction::ZoomIn) - actions.impulse(InputAction::ZoomOut)
+        + actions.axis(InputAction::ZoomOut, InputAction::ZoomIn)
+            * settings.zoom_hold_rate
+            * fast_multiplier(&actions, &settings)
+            * time.delta_secs();
 
     if scroll_y != 0.0 {
         // 根据距离调整缩放效果，使缩放更平滑自然
-        let zoom_factor = camera.distance * 0.05 * ZOOM_SPEED;
+        let zoom_factor = camera.distance * 0.05 * settings.zoom_speed;
+        let old_distance = camera.distance;
         camera.distance -= scroll_y * zoom_factor;
 
         // 限制缩放范围
-        camera.distance = camera.distance.clamp(5.0, 50.0);
+        camera.distance = camera
+            .distance
+            .clamp(settings.min_distance, settings.max_distance);
+
+        // 缩放前后以鼠标下的地面点为中心等比缩放焦点偏移，该点在屏幕上保持不动
+        if camera.zoom_to_cursor
+            && let Some(cursor) = window.cursor_position()
+            && let Some(hit) =
+                ground_raycast.cast_from_viewport(render_camera, camera_transform, cursor)
+        {
+            let scale = camera.distance / old_distance;
+            let offset = (hit.point - camera.focus).with_y(0.0);
+            camera.focus += offset * (1.0 - scale);
+        }
     }
 }
 
-// --- 5. Update 系统：边缘平移 ---
+// --- 5. Update 系统：边缘平移与键盘 / 手柄平移 ---
 
 fn camera_edge_pan(
     windows: Query<&Window, With<PrimaryWindow>>,
     mut camera_query: Query<(&mut GodViewCamera, &Transform)>,
     time: Res<Time>,
-    // 检查右键是否被按下，如果按下则不进行边缘平移
-    mouse_buttons: Res<ButtonInput<MouseButton>>,
-    // 引入 RightMouseAction 资源
-    right_mouse_action: Res<RightMouseAction>,
+    // 检查旋转键是否被按下，如果按下则不进行边缘平移
+    actions: Res<ActionState>,
+    settings: Res<CameraSettings>,
+    // 边缘平移开关在设置界面中修改
+    game_settings: Res<GameSettings>,
+    mut follow: ResMut<CameraFollow>,
 ) {
-    // 只有在右键未按下且未处于拖动状态时才进行边缘平移
-    if mouse_buttons.pressed(MouseButton::Right)
-        || *right_mouse_action == RightMouseAction::CameraDrag
-    {
+    // 只有在旋转键未按下（未在拖动旋转）时才进行边缘平移
+    if actions.pressed(InputAction::RotateCamera) {
         return;
     }
 
@@ -146,27 +246,41 @@ fn camera_edge_pan(
 
     let mut direction = Vec2::ZERO;
 
-    if let Some(position) = window.cursor_position() {
+    if game_settings.controls.edge_pan
+        && let Some(position) = window.cursor_position()
+    {
         let x_percent = position.x / window.width();
         let y_percent = position.y / window.height();
 
         // X 轴（左右）
-        if x_percent < EDGE_PAN_THRESHOLD {
+        if x_percent < settings.edge_pan_threshold {
             direction.x -= 1.0;
-        } else if x_percent > 1.0 - EDGE_PAN_THRESHOLD {
+        } else if x_percent > 1.0 - settings.edge_pan_threshold {
             direction.x += 1.0;
         }
 
         // Y 轴（上下，对应世界 Z 轴）
-        if y_percent < EDGE_PAN_THRESHOLD {
+        if y_percent < settings.edge_pan_threshold {
             direction.y += 1.0; // Y 屏幕坐标减小 (靠近顶部) 对应 Z 世界坐标增大 (向前)
-        } else if y_percent > 1.0 - EDGE_PAN_THRESHOLD {
+        } else if y_percent > 1.0 - settings.edge_pan_threshold {
             direction.y -= 1.0; // Y 屏幕坐标增大 (靠近底部) 对应 Z 世界坐标减小 (向后)
         }
     }
 
+    // 平移动作（手柄摇杆等）
+    direction += Vec2::new(
+        actions.axis(InputAction::PanLeft, InputAction::PanRight),
+        actions.axis(InputAction::PanBack, InputAction::PanForward),
+    );
+
     if direction != Vec2::ZERO {
-        let move_amount = direction.normalize() * PAN_SPEED * time.delta_secs();
+        // 玩家手动平移时放弃跟随
+        follow.release();
+
+        // 拉得越远平移越快，保证屏幕上的移动速度大致不变
+        let speed = settings.pan_speed * camera.distance / PAN_REFERENCE_DISTANCE
+            * fast_multiplier(&actions, &settings);
+        let move_amount = direction.clamp_length_max(1.0) * speed * time.delta_secs();
 
         // 获取相机在 XZ 平面上的“右”向量和“前”向量（通过忽略Y轴旋转）
         let forward_flat = transform.forward().with_y(0.0).normalize();
@@ -178,161 +292,206 @@ fn camera_edge_pan(
     }
 }
 
-// // --- 6. Update 系统：右键拖动改变视角（环绕）或判定动作 ---
-// /// 该系统负责判定右键是拖动 (CameraDrag) 还是点击 (CharacterMove)，并执行 CameraDrag 动作。
-/// 右键行为：短促点击 → 移动角色；按住并拖动 → 旋转相机
-fn camera_right_drag_rotate(
-    mut state: ResMut<CameraRotateState>,
-    mut right_mouse: ResMut<RightMouseAction>,
-    mut drag_timer: Local<f32>, // 取代复杂的 Timer
-    mut has_moved_significantly: Local<bool>,
-
-    camera_q: Query<(&GodViewCamera, &Transform)>,
+// --- 6. Update 系统：拖动改变视角（环绕） ---
+/// 旋转键的鼠标绑定（默认右键）按住拖动时旋转相机，短促点击由移动系统作为移动命令处理；
+/// 旋转键绑定到键盘时按住即可用鼠标移动旋转
+#[allow(clippy::too_many_arguments)]
+fn camera_drag_rotate(
+    settings: Res<CameraSettings>,
+    mut camera_q: Query<&mut GodViewCamera>,
+    actions: Res<ActionState>,
+    input_map: Res<InputMap>,
     mouse: Res<ButtonInput<MouseButton>>,
-    mut motion_events: MessageReader<MouseMotion>,
+    motion: Res<AccumulatedMouseMotion>,
+    mut drag_starts: MessageReader<GestureDragStart>,
+    mut drags: MessageReader<GestureDrag>,
+    mut drag_ends: MessageReader<GestureDragEnd>,
     mut cursor: Single<&mut CursorOptions>,
-    time: Res<Time>,
 ) {
-    let (camera, transform) = match camera_q.single() {
-        Ok(v) => v,
-        Err(_) => return,
+    let is_rotate_button = |button: MouseButton| {
+        input_map
+            .mouse_buttons(InputAction::RotateCamera)
+            .any(|bound| bound == button)
     };
 
-    // 读取本帧所有鼠标移动
-    let motion_delta: Vec2 = motion_events.read().map(|e| e.delta).sum();
+    // 开始拖动时捕获光标，结束时释放
+    if drag_starts.read().any(|drag| is_rotate_button(drag.button)) {
+        cursor.grab_mode = CursorGrabMode::Confined;
+        cursor.visible = false;
+    }
+    if drag_ends.read().any(|drag| is_rotate_button(drag.button)) {
+        cursor.grab_mode = CursorGrabMode::None;
+        cursor.visible = true;
+    }
 
-    // === 1. 按下瞬间 ===
-    if mouse.just_pressed(MouseButton::Right) {
-        *right_mouse = RightMouseAction::PressedJustNow;
-        *drag_timer = 0.0;
-        *has_moved_significantly = false;
-        return;
+    let mut delta: Vec2 = drags
+        .read()
+        .filter(|drag| is_rotate_button(drag.button))
+        .map(|drag| drag.delta)
+        .sum();
+    let mouse_held = input_map
+        .mouse_buttons(InputAction::RotateCamera)
+        .any(|button| mouse.pressed(button));
+    if actions.pressed(InputAction::RotateCamera) && !mouse_held {
+        delta += motion.delta;
     }
 
-    // === 2. 处理按住过程中的每一帧 ===
-    if mouse.pressed(MouseButton::Right) {
-        match *right_mouse {
-            RightMouseAction::PressedJustNow | RightMouseAction::WaitingForDecision => {
-                // 累计时间与移动距离
-                *drag_timer += time.delta_secs();
-                if motion_delta.length() > DRAG_THRESHOLD_DISTANCE {
-                    *has_moved_significantly = true;
-                }
-
-                // 一旦满足“明显拖动”条件，立即进入拖动模式
-                if *drag_timer > DRAG_THRESHOLD_TIME || *has_moved_significantly {
-                    *right_mouse = RightMouseAction::CameraDrag;
-
-                    // 初始化当前角度
-                    let (yaw, pitch, _roll) = transform.rotation.to_euler(EulerRot::YXZ);
-                    state.yaw = yaw;
-                    state.pitch = pitch;
-
-                    // 捕获光标
-                    cursor.grab_mode = CursorGrabMode::Confined;
-                    cursor.visible = false;
-                } else {
-                    // 还在犹豫期
-                    *right_mouse = RightMouseAction::WaitingForDecision;
-                }
-            }
-
-            RightMouseAction::CameraDrag => {
-                // 正在拖动 → 实时更新角度
-                if motion_delta != Vec2::ZERO {
-                    state.yaw -= motion_delta.x * camera.sensitivity;
-                    state.pitch -= motion_delta.y * camera.sensitivity;
-                    state.pitch = state.pitch.clamp(
-                        -std::f32::consts::FRAC_PI_2 + 0.01,
-                        -0.01, // 或者使用 camera.max_pitch 上限
-                    );
-                }
-            }
-
-            _ => {}
-        }
-        return; // 按住时不处理松开
+    if delta != Vec2::ZERO
+        && let Ok(mut camera) = camera_q.single_mut()
+    {
+        let sensitivity = camera.sensitivity;
+        camera.yaw -= delta.x * sensitivity;
+        camera.pitch -= delta.y * sensitivity;
+        camera.pitch = camera.pitch.clamp(settings.min_pitch, settings.max_pitch);
     }
+}
 
-    // === 3. 松开瞬间（只有在非拖动模式下才视为点击）===
-    if mouse.just_released(MouseButton::Right) {
-        match *right_mouse {
-            RightMouseAction::PressedJustNow | RightMouseAction::WaitingForDecision => {
-                // 短促点击 → 触发角色移动
-                *right_mouse = RightMouseAction::CharacterMove;
-                // 移动系统会在下一系统看到这个状态并处理，然后把它清掉
-            }
-            RightMouseAction::CameraDrag => {
-                // 结束拖动，保持当前角度（重要！）
-                *right_mouse = RightMouseAction::None;
-
-                // 释放光标
-                cursor.grab_mode = CursorGrabMode::None;
-                cursor.visible = true;
-            }
-            _ => {
-                *right_mouse = RightMouseAction::None;
-            }
-        }
+/// 暂停时中断进行中的拖拽并释放光标，避免在暂停菜单中光标被捕获
+fn cancel_camera_drag(mut gestures: ResMut<Gestures>, mut cursor: Single<&mut CursorOptions>) {
+    gestures.cancel();
+    cursor.grab_mode = CursorGrabMode::None;
+    cursor.visible = true;
+}
+
+// --- 7. Update 系统：键盘 / 手柄旋转视角 ---
+
+fn camera_keyboard_rotate(
+    mut camera_query: Query<&mut GodViewCamera>,
+    actions: Res<ActionState>,
+    settings: Res<CameraSettings>,
+    input_map: Res<InputMap>,
+    gestures: Res<Gestures>,
+    time: Res<Time>,
+) {
+    // 鼠标拖动旋转时以拖动为准
+    if input_map
+        .mouse_buttons(InputAction::RotateCamera)
+        .any(|button| gestures.is_dragging(button))
+    {
+        return;
+    }
+    let turn = actions.axis(InputAction::RotateLeft, InputAction::RotateRight);
+    if turn != 0.0
+        && let Ok(mut camera) = camera_query.single_mut()
+    {
+        camera.yaw -=
+            turn * settings.rotate_speed * fast_multiplier(&actions, &settings) * time.delta_secs();
     }
 }
 
-// --- 7. Update 系统：应用最终的 Transform ---
+pub(crate) fn fast_multiplier(actions: &ActionState, settings: &CameraSettings) -> f32 {
+    if actions.pressed(InputAction::FastCamera) {
+        settings.fast_multiplier
+    } else {
+        1.0
+    }
+}
+
+/// 把目标值限制在相机参数与地图边界之内
+fn clamp_camera_target(
+    mut camera_query: Query<&mut GodViewCamera>,
+    settings: Res<CameraSettings>,
+    bounds: Res<CameraBounds>,
+) {
+    let Ok(mut camera) = camera_query.single_mut() else {
+        return;
+    };
+    camera.distance = camera
+        .distance
+        .clamp(settings.min_distance, settings.max_distance);
+    camera.pitch = camera.pitch.clamp(settings.min_pitch, settings.max_pitch);
+    camera.focus = bounds.clamp(camera.focus);
+}
+
+// --- 8. Update 系统：应用最终的 Transform ---
 
-/// 计算基于 Yaw 和 Pitch 的旋转 Quat
+/// 当前姿态以与帧率无关的指数衰减逼近目标姿态，再写入 Transform
 fn update_camera_transform(
-    mut camera_query: Query<(&mut Transform, &GodViewCamera)>,
-    mut state: ResMut<CameraRotateState>, // 注意要 mut！
-    right_mouse_action: Res<RightMouseAction>,
+    mut camera_query: Query<(&mut Transform, &mut GodViewCamera)>,
+    time: Res<Time>,
 ) {
-    let (mut transform, camera) = match camera_query.single_mut() {
+    let (mut transform, mut camera) = match camera_query.single_mut() {
         Ok(v) => v,
         Err(_) => return,
     };
 
-    let (current_yaw, current_pitch, _) = transform.rotation.to_euler(EulerRot::YXZ);
-
-    // === 第一帧初始化：从当前相机姿态读取初始值 ===
-    if !state.has_ever_dragged {
-        state.yaw = current_yaw;
-        state.pitch = current_pitch;
-        state.last_manual_pitch = current_pitch;
-        state.has_ever_dragged = true; // 防止下次再初始化
+    // 刚生成的相机直接到位，不从默认姿态滑过去
+    if camera.is_added() {
+        camera.snap();
     }
 
-    let target_yaw: f32;
-    let target_pitch: f32;
-
-    if *right_mouse_action == RightMouseAction::CameraDrag {
-        // 拖动中：实时使用 state 中的值（已在拖动系统里更新）
-        target_yaw = state.yaw;
-        target_pitch = state.pitch;
-
-        // 重要！实时记录玩家手动调整的 pitch
-        state.last_manual_pitch = state.pitch;
+    let t = if camera.smoothing > 0.0 {
+        1.0 - (-camera.smoothing * time.delta_secs()).exp()
     } else {
-        // 非拖动状态：
-        // - Yaw：保持玩家最后拖动时的 yaw（自由旋转）
-        // - Pitch：保持玩家最后手动调整的 pitch（不再回 default！）
-        target_yaw = state.yaw;
-        target_pitch = state.last_manual_pitch;
-    }
-
-    // 限制 pitch 范围（防止翻转）
-    let target_pitch = target_pitch.clamp(
-        -std::f32::consts::FRAC_PI_2 + 0.05,
-        -0.05, // 或者使用 camera.min_pitch / max_pitch
-    );
-
-    let rotation = Quat::from_euler(EulerRot::YXZ, target_yaw, target_pitch, 0.0);
+        1.0
+    };
+    let target = camera.target();
+    camera.current = camera.current.lerp(&target, t);
 
     // 相机围绕焦点旋转
-    let offset = rotation * Vec3::new(0.0, 0.0, camera.distance);
-    transform.translation = camera.focus + offset;
-    transform.rotation = rotation;
+    transform.translation = camera.current.translation();
+    transform.rotation = camera.current.rotation();
 }
 
 pub fn calculate_rotation(yaw: f32, pitch: f32) -> Quat {
     // Bevy 的标准旋转顺序通常是 YXZ (Yaw, Pitch, Roll)
     Quat::from_axis_angle(Vec3::Y, yaw) * Quat::from_axis_angle(Vec3::X, pitch)
 }
+
+// --- 9. Update 系统：避免相机穿进场景 ---
+
+/// 从焦点沿视线向相机做射线检测，被遮挡时立即把相机拉到遮挡物前，遮挡消失后再平滑退回原距离
+fn avoid_camera_collision(
+    mut camera_query: Query<(&mut Transform, &GodViewCamera)>,
+    settings: Res<CameraSettings>,
+    mut ground_raycast: GroundRaycast,
+    time: Res<Time>,
+    // 当前被拉近后的距离，None 表示没有被拉近
+    mut pulled_distance: Local<Option<f32>>,
+) {
+    let Ok((mut transform, camera)) = camera_query.single_mut() else {
+        return;
+    };
+    if !settings.collision_avoidance {
+        *pulled_distance = None;
+        return;
+    }
+
+    let pose = camera.current;
+    let Ok(direction) = Dir3::new(pose.rotation() * Vec3::Z) else {
+        return;
+    };
+    let start = pose.focus + direction * COLLISION_PROBE_START;
+    let limit = ground_raycast
+        .cast_ray(Ray3d::new(start, direction))
+        .map(|hit| hit.point.distance(pose.focus) - settings.collision_margin)
+        .filter(|limit| *limit < pose.distance)
+        .map(|limit| limit.max(COLLISION_MIN_DISTANCE))
+        .unwrap_or(pose.distance);
+
+    let current = pulled_distance.unwrap_or(pose.distance);
+    let distance = if limit <= current {
+        limit
+    } else {
+        let t = 1.0 - (-settings.collision_recover_speed * time.delta_secs()).exp();
+        current + (limit - current) * t
+    };
+    *pulled_distance = (distance < pose.distance - 0.01).then_some(distance);
+
+    if let Some(distance) = *pulled_distance {
+        transform.translation = pose.focus + direction * distance;
+    }
+}
+
+// --- 10. 离开游戏：清理按地图区分的相机状态 ---
+
+/// 边界、跟随目标与书签都属于当前这局游戏，回到主菜单时恢复默认，避免带入下一局
+fn reset_camera_state(
+    mut bounds: ResMut<CameraBounds>,
+    mut follow: ResMut<CameraFollow>,
+    mut bookmarks: ResMut<CameraBookmarks>,
+) {
+    *bounds = CameraBounds::default();
+    follow.release();
+    *bookmarks = CameraBookmarks::default();
+}
@@ -1 +1,6 @@
-pub mod god_view_camera;
\ No newline at end of file
+pub mod bookmarks;
+pub mod cinematic;
+pub mod follow;
+pub mod free_fly;
+pub mod god_view_camera;
+pub mod settings;
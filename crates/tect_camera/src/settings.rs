@@ -0,0 +1,126 @@
+///相机参数与地图边界：缩放 / 俯仰范围、平移与旋转速度从 config/camera.ron 读取
+///地图场景加载完成后根据 Ground 网格的范围生成 CameraBounds，相机焦点不会离开地图
+use bevy::prelude::*;
+use bevy::scene::SceneInstanceReady;
+use serde::{Deserialize, Serialize};
+use std::f32::consts::FRAC_PI_2;
+use tect_control::moving::Ground;
+use tect_control::scene_mesh::SceneMeshes;
+use tect_state::config::load_or_create;
+use tect_state::settings::GameSettings;
+
+use crate::god_view_camera::GodViewCamera;
+
+/// 相机参数的保存位置
+pub const CAMERA_SETTINGS_PATH: &str = "config/camera.ron";
+
+/// 相机参数
+#[derive(Resource, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CameraSettings {
+    /// 最近距离
+    pub min_distance: f32,
+    /// 最远距离
+    pub max_distance: f32,
+    /// 最低俯仰角（最接近垂直俯视）
+    pub min_pitch: f32,
+    /// 最高俯仰角（最接近水平）
+    pub max_pitch: f32,
+    /// 相机平移速度
+    pub pan_speed: f32,
+    /// 窗口边缘多大比例内触发平移（推屏开关在 GameSettings 的操作设置中）
+    pub edge_pan_threshold: f32,
+    /// 滚轮缩放速度
+    pub zoom_speed: f32,
+    /// 按住缩放键（手柄扳机）时每秒缩放的格数
+    pub zoom_hold_rate: f32,
+    /// 键盘旋转速度（弧度/秒）
+    pub rotate_speed: f32,
+    /// 按住加速键时的速度倍数
+    pub fast_multiplier: f32,
+    /// 焦点与相机之间被场景遮挡时把相机拉近
+    pub collision_avoidance: bool,
+    /// 拉近时与遮挡面保持的距离
+    pub collision_margin: f32,
+    /// 遮挡消失后恢复原距离的速度（1/秒）
+    pub collision_recover_speed: f32,
+}
+
+impl Default for CameraSettings {
+    fn default() -> Self {
+        Self {
+            min_distance: 5.0,
+            max_distance: 50.0,
+            min_pitch: -FRAC_PI_2 + 0.05,
+            max_pitch: -0.05,
+            pan_speed: 5.0,
+            edge_pan_threshold: 0.005,
+            zoom_speed: 1.0,
+            zoom_hold_rate: 8.0,
+            rotate_speed: 1.5,
+            fast_multiplier: 3.0,
+            collision_avoidance: true,
+            collision_margin: 0.3,
+            collision_recover_speed: 4.0,
+        }
+    }
+}
+
+/// 相机焦点允许的范围（XZ 平面，Rect 的 y 对应世界 Z），None 表示不限制
+#[derive(Resource, Debug, Clone, Copy, Default, PartialEq)]
+pub struct CameraBounds(pub Option<Rect>);
+
+impl CameraBounds {
+    /// 把焦点限制在范围内，保持高度不变
+    pub fn clamp(&self, focus: Vec3) -> Vec3 {
+        match self.0 {
+            Some(rect) => {
+                let xz = Vec2::new(focus.x, focus.z).clamp(rect.min, rect.max);
+                Vec3::new(xz.x, focus.y, xz.y)
+            }
+            None => focus,
+        }
+    }
+}
+
+pub(crate) fn load_camera_settings(mut commands: Commands) {
+    commands.insert_resource(load_or_create::<CameraSettings>(CAMERA_SETTINGS_PATH));
+}
+
+/// 应用设置界面中的拖动旋转灵敏度，新生成的相机同样适用
+pub(crate) fn apply_control_settings(
+    game_settings: Res<GameSettings>,
+    mut cameras: Query<&mut GodViewCamera>,
+) {
+    let controls = &game_settings.controls;
+    let changed = game_settings.is_changed();
+    for mut camera in &mut cameras {
+        if changed || camera.is_added() {
+            camera.sensitivity = controls.camera_sensitivity;
+        }
+    }
+}
+
+/// 地图场景实例化完成后，以 Ground 网格的水平范围作为相机边界
+pub(crate) fn camera_bounds_from_ground(
+    ready: On<SceneInstanceReady>,
+    grounds: Query<(), With<Ground>>,
+    scene_meshes: SceneMeshes,
+    mut bounds: ResMut<CameraBounds>,
+) {
+    if !grounds.contains(ready.entity) {
+        return;
+    }
+    let points = scene_meshes
+        .triangles(ready.entity)
+        .into_iter()
+        .flatten()
+        .map(|p| Vec2::new(p.x, p.z));
+    let rect = points.fold(None, |rect: Option<Rect>, p| {
+        Some(rect.map_or(Rect::from_corners(p, p), |r| r.union_point(p)))
+    });
+    if let Some(rect) = rect {
+        info!("相机边界: {:?} ~ {:?}", rect.min, rect.max);
+        bounds.0 = Some(rect);
+    }
+}
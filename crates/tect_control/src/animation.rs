@@ -0,0 +1,267 @@
+///角色动画状态机：根据移动速度与是否有移动目标，在待机 / 行走 / 奔跑动画之间交叉淡入淡出
+///每个角色模型通过 CharacterAnimationSet 配置自己的动画片段，生成角色时与 PlayerMove 一起插入即可
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tect_state::app_state::*;
+
+use crate::moving::PlayerMove;
+
+pub struct CharacterAnimationPlugin;
+
+impl Plugin for CharacterAnimationPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_observer(build_character_animation_graph)
+            .add_systems(
+                Update,
+                (
+                    bind_character_animation_players,
+                    update_character_animations,
+                )
+                    .run_if(in_state(AppState::InGame))
+                    .chain(),
+            );
+    }
+}
+
+// 速度平滑系数，越大越跟手
+const SPEED_SMOOTHING: f32 = 10.0;
+
+/// 移动动画状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Locomotion {
+    Idle,
+    Walk,
+    Run,
+}
+
+/// 动画片段来源：glTF 文件中的第几个动画
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnimationClipSource {
+    pub path: String,
+    pub index: usize,
+    /// 制作动画时对应的移动速度（米/秒），播放速度按实际速度等比缩放；为 0 时按原速播放
+    #[serde(default)]
+    pub authored_speed: f32,
+}
+
+impl AnimationClipSource {
+    pub fn new(path: impl Into<String>, index: usize) -> Self {
+        Self {
+            path: path.into(),
+            index,
+            authored_speed: 0.0,
+        }
+    }
+
+    pub fn with_authored_speed(mut self, speed: f32) -> Self {
+        self.authored_speed = speed;
+        self
+    }
+}
+
+/// 角色模型的动画配置，缺少的片段会退回到相近的状态（奔跑 ↔ 行走），没有待机动画时停止播放
+#[derive(Component, Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CharacterAnimationSet {
+    pub idle: Option<AnimationClipSource>,
+    pub walk: Option<AnimationClipSource>,
+    pub run: Option<AnimationClipSource>,
+    /// 实际速度低于该值视为待机
+    pub idle_speed: f32,
+    /// 实际速度达到该值切换为奔跑
+    pub run_speed: f32,
+    /// 状态切换时的淡入淡出时长
+    pub fade: Duration,
+}
+
+impl Default for CharacterAnimationSet {
+    fn default() -> Self {
+        Self {
+            idle: None,
+            walk: None,
+            run: None,
+            idle_speed: 0.1,
+            run_speed: 3.5,
+            fade: Duration::from_millis(250),
+        }
+    }
+}
+
+impl CharacterAnimationSet {
+    fn clip(&self, state: Locomotion) -> Option<&AnimationClipSource> {
+        match state {
+            Locomotion::Idle => self.idle.as_ref(),
+            Locomotion::Walk => self.walk.as_ref().or(self.run.as_ref()),
+            Locomotion::Run => self.run.as_ref().or(self.walk.as_ref()),
+        }
+    }
+
+    fn state_for(&self, speed: f32, has_target: bool) -> Locomotion {
+        if !has_target || speed < self.idle_speed {
+            Locomotion::Idle
+        } else if speed >= self.run_speed {
+            Locomotion::Run
+        } else {
+            Locomotion::Walk
+        }
+    }
+}
+
+/// 动画状态机的运行时数据，由 CharacterAnimationSet 自动生成
+#[derive(Component, Debug)]
+pub struct CharacterAnimator {
+    graph: Handle<AnimationGraph>,
+    idle: Option<AnimationNodeIndex>,
+    walk: Option<AnimationNodeIndex>,
+    run: Option<AnimationNodeIndex>,
+    /// 场景实例化后找到的动画播放器实体
+    player: Option<Entity>,
+    state: Option<Locomotion>,
+    last_position: Option<Vec3>,
+    /// 平滑后的水平移动速度
+    speed: f32,
+}
+
+impl CharacterAnimator {
+    pub fn state(&self) -> Option<Locomotion> {
+        self.state
+    }
+
+    pub fn speed(&self) -> f32 {
+        self.speed
+    }
+
+    fn node(&self, state: Locomotion) -> Option<AnimationNodeIndex> {
+        match state {
+            Locomotion::Idle => self.idle,
+            Locomotion::Walk => self.walk.or(self.run),
+            Locomotion::Run => self.run.or(self.walk),
+        }
+    }
+}
+
+// ──────────────────────────────────────────────────────────────
+// 1. 根据配置构建动画图
+// ──────────────────────────────────────────────────────────────
+fn build_character_animation_graph(
+    add: On<Add, CharacterAnimationSet>,
+    mut commands: Commands,
+    sets: Query<&CharacterAnimationSet>,
+    asset_server: Res<AssetServer>,
+    mut graphs: ResMut<Assets<AnimationGraph>>,
+) {
+    let Ok(set) = sets.get(add.entity) else {
+        return;
+    };
+
+    let mut graph = AnimationGraph::new();
+    let mut add_clip = |source: Option<&AnimationClipSource>| {
+        source.map(|source| {
+            let clip = asset_server
+                .load(GltfAssetLabel::Animation(source.index).from_asset(source.path.clone()));
+            graph.add_clip(clip, 1.0, graph.root)
+        })
+    };
+    let idle = add_clip(set.idle.as_ref());
+    let walk = add_clip(set.walk.as_ref());
+    let run = add_clip(set.run.as_ref());
+
+    commands.entity(add.entity).insert(CharacterAnimator {
+        graph: graphs.add(graph),
+        idle,
+        walk,
+        run,
+        player: None,
+        state: None,
+        last_position: None,
+        speed: 0.0,
+    });
+}
+
+// ──────────────────────────────────────────────────────────────
+// 2. 场景加载完成后把动画图绑定到模型的动画播放器
+// ──────────────────────────────────────────────────────────────
+fn bind_character_animation_players(
+    mut commands: Commands,
+    players: Query<Entity, Added<AnimationPlayer>>,
+    parents: Query<&ChildOf>,
+    mut animators: Query<&mut CharacterAnimator>,
+) {
+    for entity in players.iter() {
+        let Some(root) = parents
+            .iter_ancestors(entity)
+            .find(|ancestor| animators.contains(*ancestor))
+        else {
+            continue;
+        };
+        let Ok(mut animator) = animators.get_mut(root) else {
+            continue;
+        };
+        commands.entity(entity).insert((
+            AnimationGraphHandle(animator.graph.clone()),
+            AnimationTransitions::new(),
+        ));
+        animator.player = Some(entity);
+        // 重新进入一次状态，确保绑定后立即开始播放
+        animator.state = None;
+    }
+}
+
+// ──────────────────────────────────────────────────────────────
+// 3. 状态切换与播放速度
+// ──────────────────────────────────────────────────────────────
+fn update_character_animations(
+    time: Res<Time>,
+    mut characters: Query<(
+        &Transform,
+        &PlayerMove,
+        &CharacterAnimationSet,
+        &mut CharacterAnimator,
+    )>,
+    mut players: Query<(&mut AnimationPlayer, &mut AnimationTransitions)>,
+) {
+    let dt = time.delta_secs();
+    if dt <= 0.0 {
+        return;
+    }
+    for (transform, player_move, set, mut animator) in characters.iter_mut() {
+        // 按实际位移计算速度，碰撞阻挡、加减速都会体现在动画上
+        let position = transform.translation;
+        let measured = animator
+            .last_position
+            .map_or(0.0, |last| (position - last).with_y(0.0).length() / dt);
+        animator.last_position = Some(position);
+        animator.speed += (measured - animator.speed) * (1.0 - (-SPEED_SMOOTHING * dt).exp());
+
+        let Some(player_entity) = animator.player else {
+            continue;
+        };
+        let Ok((mut player, mut transitions)) = players.get_mut(player_entity) else {
+            continue;
+        };
+
+        let state = set.state_for(animator.speed, player_move.target_position.is_some());
+        if animator.state != Some(state) {
+            animator.state = Some(state);
+            match animator.node(state) {
+                Some(node) => {
+                    transitions.play(&mut player, node, set.fade).repeat();
+                }
+                None => {
+                    player.stop_all();
+                }
+            }
+        }
+
+        // 行走 / 奔跑动画的播放速度随实际速度缩放，避免脚底打滑
+        if let Some(source) = set.clip(state)
+            && source.authored_speed > 0.0
+            && state != Locomotion::Idle
+            && let Some(node) = animator.node(state)
+            && let Some(active) = player.animation_mut(node)
+        {
+            active.set_speed((animator.speed / source.authored_speed).clamp(0.25, 2.0));
+        }
+    }
+}
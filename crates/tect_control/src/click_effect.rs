@@ -0,0 +1,272 @@
+///右键点击特效：在命中点播放一次性动画，播放完毕后整个特效层级回收进对象池，池满时直接销毁
+///同时存在的特效数量有上限，达到上限后复用最早生成的特效，频繁点击不会无限分配新实体
+use bevy::animation::RepeatAnimation;
+use bevy::prelude::*;
+use std::collections::VecDeque;
+use std::time::Duration;
+use tect_state::app_state::*;
+use tect_state::loading::LoadingAssets;
+
+pub struct ClickEffectPlugin;
+
+impl Plugin for ClickEffectPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ClickEffectSettings>()
+            .init_resource::<ClickEffectPool>()
+            .add_message::<SpawnClickEffect>()
+            .add_systems(Startup, load_click_effect_assets)
+            .add_systems(OnEnter(AppState::Loading), preload_click_effect_assets)
+            .add_systems(
+                Update,
+                (
+                    spawn_click_effects,
+                    setup_click_effect_once_loaded,
+                    recycle_finished_click_effects,
+                )
+                    .run_if(in_state(AppState::InGame))
+                    .chain(),
+            )
+            .add_systems(OnExit(AppState::InGame), clear_click_effect_pool);
+    }
+}
+
+// 特效离命中表面的距离，避免与地面 Z-fighting
+const SURFACE_LIFT: f32 = 0.02;
+
+// ──────────────────────────────────────────────────────────────
+// 1. 资源与配置
+// ──────────────────────────────────────────────────────────────
+
+/// 预加载的特效场景 + 动画图
+#[derive(Resource)]
+pub struct ClickEffectAssets {
+    pub scene: Handle<Scene>,
+    pub graph: Handle<AnimationGraph>,
+    pub click_animation: AnimationNodeIndex, // 我们只用一个“Click”动画
+}
+
+#[derive(Resource, Debug, Clone)]
+pub struct ClickEffectSettings {
+    /// 同时播放的特效上限，达到上限后复用最早的特效
+    pub max_live: usize,
+    /// 对象池中保留的空闲特效上限，多出的特效播放完直接销毁
+    pub max_pooled: usize,
+    /// 最长存活时间（秒），动画迟迟没有结束（例如资源缺失）时也会被回收
+    pub max_lifetime: f32,
+}
+
+impl Default for ClickEffectSettings {
+    fn default() -> Self {
+        Self {
+            max_live: 8,
+            max_pooled: 8,
+            max_lifetime: 3.0,
+        }
+    }
+}
+
+/// 请求在地面命中点生成一个点击特效
+#[derive(Message, Debug, Clone, Copy)]
+pub struct SpawnClickEffect {
+    pub position: Vec3,
+    /// 命中表面的法线，特效贴合表面朝向
+    pub normal: Vec3,
+}
+
+/// 特效场景的根实体
+#[derive(Component, Debug, Default)]
+pub struct ClickEffect {
+    /// 场景实例化后找到的动画播放器实体
+    player: Option<Entity>,
+    /// 本次播放已经存活的时间
+    age: f32,
+}
+
+/// 特效对象池：正在播放的按生成顺序排列，空闲的处于隐藏状态等待复用
+#[derive(Resource, Debug, Default)]
+struct ClickEffectPool {
+    live: VecDeque<Entity>,
+    idle: Vec<Entity>,
+}
+
+///初始化右键动画资源
+fn load_click_effect_assets(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut graphs: ResMut<Assets<AnimationGraph>>,
+) {
+    let scene_handle: Handle<Scene> =
+        asset_server.load(GltfAssetLabel::Scene(0).from_asset("rola/rola_run.glb"));
+
+    // 假设你的 glTF 中有一个名为 "Click" 的动画（索引 0）
+    let (graph, animation_indices) = AnimationGraph::from_clips([
+        asset_server.load(GltfAssetLabel::Animation(0).from_asset("rola/rola_run.glb"))
+    ]);
+    let graph_handle = graphs.add(graph);
+
+    commands.insert_resource(ClickEffectAssets {
+        scene: scene_handle,
+        graph: graph_handle,
+        click_animation: animation_indices[0],
+    });
+}
+
+/// 进入游戏前等待特效场景加载完成，第一次点击时不会出现空白
+fn preload_click_effect_assets(
+    effect_assets: Res<ClickEffectAssets>,
+    mut loading: ResMut<LoadingAssets>,
+) {
+    let label = effect_assets
+        .scene
+        .path()
+        .map_or_else(|| "click effect".to_string(), ToString::to_string);
+    loading.add(label, effect_assets.scene.clone());
+}
+
+// ──────────────────────────────────────────────────────────────
+// 2. 生成：优先复用，达到上限时挪用最早的特效
+// ──────────────────────────────────────────────────────────────
+fn spawn_click_effects(
+    mut commands: Commands,
+    mut requests: MessageReader<SpawnClickEffect>,
+    effect_assets: Res<ClickEffectAssets>,
+    settings: Res<ClickEffectSettings>,
+    mut pool: ResMut<ClickEffectPool>,
+    mut effects: Query<(&mut ClickEffect, &mut Transform, &mut Visibility)>,
+    mut players: Query<(&mut AnimationPlayer, &mut AnimationTransitions)>,
+) {
+    for request in requests.read() {
+        let transform =
+            Transform::from_translation(request.position + request.normal * SURFACE_LIFT)
+                .looking_to(request.normal, Vec3::Y);
+
+        let reused = if pool.live.len() >= settings.max_live.max(1) {
+            pool.live.pop_front()
+        } else {
+            pool.idle.pop()
+        };
+
+        // 池中的实体可能已被外部销毁，此时退回到新建
+        if let Some(entity) = reused
+            && let Ok((mut effect, mut effect_transform, mut visibility)) = effects.get_mut(entity)
+        {
+            *effect_transform = transform;
+            *visibility = Visibility::Visible;
+            effect.age = 0.0;
+            // 从头重播；场景还没加载完时由 setup_click_effect_once_loaded 开始播放
+            if let Some(player) = effect.player
+                && let Ok((mut player, mut transitions)) = players.get_mut(player)
+            {
+                play_once(&mut player, &mut transitions, effect_assets.click_animation);
+            }
+            pool.live.push_back(entity);
+            continue;
+        }
+
+        let entity = commands
+            .spawn((
+                SceneRoot(effect_assets.scene.clone()),
+                transform,
+                Visibility::Visible,
+                ClickEffect::default(),
+                DespawnOnExit(AppState::InGame),
+            ))
+            .id();
+        pool.live.push_back(entity);
+    }
+}
+
+fn play_once(
+    player: &mut AnimationPlayer,
+    transitions: &mut AnimationTransitions,
+    animation: AnimationNodeIndex,
+) {
+    // AnimationTransitions::play 会把动画重置到开头
+    transitions
+        .play(player, animation, Duration::ZERO)
+        .set_repeat(RepeatAnimation::Count(1));
+}
+
+// ──────────────────────────────────────────────────────────────
+// 3. 场景加载完成后绑定动画图并播放一次
+// ──────────────────────────────────────────────────────────────
+fn setup_click_effect_once_loaded(
+    mut commands: Commands,
+    effect_assets: Res<ClickEffectAssets>,
+    mut players: Query<(Entity, &mut AnimationPlayer), Added<AnimationPlayer>>,
+    parents: Query<&ChildOf>,
+    mut effects: Query<&mut ClickEffect>,
+) {
+    for (entity, mut player) in players.iter_mut() {
+        // 只处理属于点击特效的播放器，角色模型的播放器不在这里接管
+        let Some(root) = parents
+            .iter_ancestors(entity)
+            .find(|ancestor| effects.contains(*ancestor))
+        else {
+            continue;
+        };
+        let Ok(mut effect) = effects.get_mut(root) else {
+            continue;
+        };
+
+        let mut transitions = AnimationTransitions::new();
+        play_once(&mut player, &mut transitions, effect_assets.click_animation);
+        commands.entity(entity).insert((
+            AnimationGraphHandle(effect_assets.graph.clone()),
+            transitions,
+        ));
+        effect.player = Some(entity);
+    }
+}
+
+// ──────────────────────────────────────────────────────────────
+// 4. 回收：动画播放完（或超时）后隐藏放回池中，池满则销毁整个特效层级
+// ──────────────────────────────────────────────────────────────
+fn recycle_finished_click_effects(
+    mut commands: Commands,
+    time: Res<Time>,
+    settings: Res<ClickEffectSettings>,
+    mut pool: ResMut<ClickEffectPool>,
+    mut effects: Query<(&mut ClickEffect, &mut Visibility)>,
+    players: Query<&AnimationPlayer>,
+) {
+    let mut finished = Vec::new();
+    for &entity in pool.live.iter() {
+        let Ok((mut effect, _)) = effects.get_mut(entity) else {
+            // 已被外部销毁，只需从池中移除
+            finished.push(entity);
+            continue;
+        };
+        effect.age += time.delta_secs();
+        let animation_done = effect
+            .player
+            .and_then(|player| players.get(player).ok())
+            .is_some_and(AnimationPlayer::all_finished);
+        if animation_done || effect.age >= settings.max_lifetime {
+            finished.push(entity);
+        }
+    }
+    if finished.is_empty() {
+        return;
+    }
+    pool.live.retain(|entity| !finished.contains(entity));
+
+    for entity in finished {
+        let Ok((_, mut visibility)) = effects.get_mut(entity) else {
+            continue;
+        };
+        if pool.idle.len() < settings.max_pooled {
+            *visibility = Visibility::Hidden;
+            pool.idle.push(entity);
+        } else {
+            // 根实体的 despawn 会连同场景子层级一起销毁
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+/// 离开游戏时特效随世界一起销毁，清空对象池中的失效实体
+fn clear_click_effect_pool(mut pool: ResMut<ClickEffectPool>) {
+    pool.live.clear();
+    pool.idle.clear();
+}
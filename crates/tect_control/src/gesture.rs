@@ -0,0 +1,455 @@
+///鼠标手势识别：对任意鼠标按键识别单击、双击、长按与拖动（开始 / 进行中 / 结束），以消息的形式发出
+///相机旋转订阅拖动、移动命令订阅单击，不再通过共享的可变状态互相协调；判定阈值从 config/gestures.ron 读取
+use bevy::input::mouse::AccumulatedMouseMotion;
+use bevy::input::InputSystems;
+use bevy::platform::collections::HashMap;
+use bevy::prelude::*;
+use bevy::ui::UiSystems;
+use bevy::window::PrimaryWindow;
+use serde::{Deserialize, Serialize};
+use tect_state::config::load_or_create;
+use tect_state::input::{ActionState, RebindState};
+
+use crate::raycast::pointer_over_ui;
+
+/// 手势阈值的保存位置
+pub const GESTURE_SETTINGS_PATH: &str = "config/gestures.ron";
+
+pub struct GesturePlugin;
+
+impl Plugin for GesturePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GestureSettings>()
+            .init_resource::<Gestures>()
+            .add_message::<GestureClick>()
+            .add_message::<GestureDoubleClick>()
+            .add_message::<GestureLongPress>()
+            .add_message::<GestureDragStart>()
+            .add_message::<GestureDrag>()
+            .add_message::<GestureDragEnd>()
+            .add_systems(Startup, load_gesture_settings)
+            .add_systems(
+                PreUpdate,
+                recognize_gestures
+                    .after(InputSystems)
+                    .after(UiSystems::Focus),
+            );
+    }
+}
+
+// ──────────────────────────────────────────────────────────────
+// 1. 阈值与消息
+// ──────────────────────────────────────────────────────────────
+
+/// 手势判定阈值
+#[derive(Resource, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GestureSettings {
+    /// 按下后鼠标累计移动超过该像素数即开始拖动
+    pub drag_distance: f32,
+    /// 按住不动超过该时长（秒）视为长按，松开时不再算单击
+    pub long_press_time: f32,
+    /// 两次单击间隔小于该时长（秒）视为双击
+    pub double_click_time: f32,
+    /// 两次单击位置相距小于该像素数才视为双击
+    pub double_click_distance: f32,
+}
+
+impl Default for GestureSettings {
+    fn default() -> Self {
+        Self {
+            drag_distance: 5.0,
+            long_press_time: 0.3,
+            double_click_time: 0.3,
+            double_click_distance: 10.0,
+        }
+    }
+}
+
+/// 短促点击，在松开时发出；双击的第二次点击同样会发出
+#[derive(Message, Debug, Clone, Copy)]
+pub struct GestureClick {
+    pub button: MouseButton,
+    pub position: Vec2,
+}
+
+/// 双击，在第二次点击松开时发出（紧随 GestureClick）
+#[derive(Message, Debug, Clone, Copy)]
+pub struct GestureDoubleClick {
+    pub button: MouseButton,
+    pub position: Vec2,
+}
+
+/// 按住不动达到长按时长
+#[derive(Message, Debug, Clone, Copy)]
+pub struct GestureLongPress {
+    pub button: MouseButton,
+    pub position: Vec2,
+}
+
+/// 开始拖动
+#[derive(Message, Debug, Clone, Copy)]
+pub struct GestureDragStart {
+    pub button: MouseButton,
+    /// 按下时的位置
+    pub start: Vec2,
+}
+
+/// 拖动中鼠标移动，光标被锁定时 delta 依然有效
+#[derive(Message, Debug, Clone, Copy)]
+pub struct GestureDrag {
+    pub button: MouseButton,
+    pub delta: Vec2,
+}
+
+/// 结束拖动
+#[derive(Message, Debug, Clone, Copy)]
+pub struct GestureDragEnd {
+    pub button: MouseButton,
+    pub start: Vec2,
+    pub position: Vec2,
+}
+
+// ──────────────────────────────────────────────────────────────
+// 2. 识别状态
+// ──────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy)]
+struct Press {
+    start: Vec2,
+    held: f32,
+    travel: f32,
+    dragging: bool,
+    long_pressed: bool,
+}
+
+/// 各按键的手势识别状态
+#[derive(Resource, Debug, Default)]
+pub struct Gestures {
+    presses: HashMap<MouseButton, Press>,
+    /// 上一次单击的位置与时间，用于判定双击
+    last_clicks: HashMap<MouseButton, (Vec2, f32)>,
+}
+
+impl Gestures {
+    /// 该按键是否正在拖动
+    pub fn is_dragging(&self, button: MouseButton) -> bool {
+        self.presses
+            .get(&button)
+            .is_some_and(|press| press.dragging)
+    }
+
+    /// 放弃所有进行中的按压，已按下的键松开时不再产生手势
+    pub fn cancel(&mut self) {
+        self.presses.clear();
+        self.last_clicks.clear();
+    }
+}
+
+fn load_gesture_settings(mut commands: Commands) {
+    commands.insert_resource(load_or_create::<GestureSettings>(GESTURE_SETTINGS_PATH));
+}
+
+// ──────────────────────────────────────────────────────────────
+// 3. 识别
+// ──────────────────────────────────────────────────────────────
+#[allow(clippy::too_many_arguments)]
+fn recognize_gestures(
+    time: Res<Time>,
+    settings: Res<GestureSettings>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    motion: Res<AccumulatedMouseMotion>,
+    window: Single<&Window, With<PrimaryWindow>>,
+    actions: Res<ActionState>,
+    rebind: Res<RebindState>,
+    interactions: Query<&Interaction>,
+    mut gestures: ResMut<Gestures>,
+    mut clicks: MessageWriter<GestureClick>,
+    mut double_clicks: MessageWriter<GestureDoubleClick>,
+    mut long_presses: MessageWriter<GestureLongPress>,
+    mut drag_starts: MessageWriter<GestureDragStart>,
+    mut drags: MessageWriter<GestureDrag>,
+    mut drag_ends: MessageWriter<GestureDragEnd>,
+) {
+    // 输入被挂起（过场镜头）或正在改键时不识别手势
+    if actions.is_suspended() || *rebind != RebindState::Idle {
+        gestures.cancel();
+        return;
+    }
+
+    let cursor = window.cursor_position();
+    let now = time.elapsed_secs();
+    let Gestures {
+        presses,
+        last_clicks,
+    } = gestures.as_mut();
+
+    // 在 UI 上按下的键交给 UI 处理
+    if !pointer_over_ui(&interactions) {
+        for &button in mouse.get_just_pressed() {
+            presses.insert(
+                button,
+                Press {
+                    start: cursor.unwrap_or_default(),
+                    held: 0.0,
+                    travel: 0.0,
+                    dragging: false,
+                    long_pressed: false,
+                },
+            );
+        }
+    }
+
+    for (&button, press) in presses.iter_mut() {
+        if !mouse.pressed(button) {
+            continue;
+        }
+        press.held += time.delta_secs();
+        press.travel += motion.delta.length();
+
+        if !press.dragging && press.travel > settings.drag_distance {
+            press.dragging = true;
+            drag_starts.write(GestureDragStart {
+                button,
+                start: press.start,
+            });
+        }
+        if press.dragging {
+            if motion.delta != Vec2::ZERO {
+                drags.write(GestureDrag {
+                    button,
+                    delta: motion.delta,
+                });
+            }
+        } else if !press.long_pressed && press.held >= settings.long_press_time {
+            press.long_pressed = true;
+            long_presses.write(GestureLongPress {
+                button,
+                position: press.start,
+            });
+        }
+    }
+
+    for &button in mouse.get_just_released() {
+        let Some(press) = presses.remove(&button) else {
+            continue;
+        };
+        let position = cursor.unwrap_or(press.start);
+        if press.dragging {
+            drag_ends.write(GestureDragEnd {
+                button,
+                start: press.start,
+                position,
+            });
+            continue;
+        }
+        if press.long_pressed {
+            continue;
+        }
+
+        clicks.write(GestureClick { button, position });
+        match last_clicks.remove(&button) {
+            Some((last_position, last_time))
+                if now - last_time <= settings.double_click_time
+                    && last_position.distance(position) <= settings.double_click_distance =>
+            {
+                double_clicks.write(GestureDoubleClick { button, position });
+            }
+            _ => {
+                last_clicks.insert(button, (position, now));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::time::TimeUpdateStrategy;
+    use std::time::Duration;
+
+    const BUTTON: MouseButton = MouseButton::Left;
+
+    /// 每帧固定前进 100ms、只运行 recognize_gestures 的最小 App；按键与鼠标移动由测试直接写入
+    fn gesture_app() -> App {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+                100,
+            )))
+            .init_resource::<GestureSettings>()
+            .init_resource::<Gestures>()
+            .init_resource::<ActionState>()
+            .init_resource::<RebindState>()
+            .init_resource::<ButtonInput<MouseButton>>()
+            .init_resource::<AccumulatedMouseMotion>()
+            .add_message::<GestureClick>()
+            .add_message::<GestureDoubleClick>()
+            .add_message::<GestureLongPress>()
+            .add_message::<GestureDragStart>()
+            .add_message::<GestureDrag>()
+            .add_message::<GestureDragEnd>()
+            .add_systems(Update, recognize_gestures)
+            .add_systems(Last, end_input_frame);
+        let mut window = Window::default();
+        window.set_cursor_position(Some(Vec2::new(100.0, 100.0)));
+        app.world_mut().spawn((window, PrimaryWindow));
+        app
+    }
+
+    /// 代替 InputPlugin 在帧末清除 just_pressed / just_released 与本帧的鼠标移动
+    fn end_input_frame(
+        mut mouse: ResMut<ButtonInput<MouseButton>>,
+        mut motion: ResMut<AccumulatedMouseMotion>,
+    ) {
+        mouse.clear();
+        motion.delta = Vec2::ZERO;
+    }
+
+    fn press(app: &mut App) {
+        app.world_mut()
+            .resource_mut::<ButtonInput<MouseButton>>()
+            .press(BUTTON);
+        app.update();
+    }
+
+    fn release(app: &mut App) {
+        app.world_mut()
+            .resource_mut::<ButtonInput<MouseButton>>()
+            .release(BUTTON);
+        app.update();
+    }
+
+    fn move_mouse(app: &mut App, delta: Vec2) {
+        app.world_mut()
+            .resource_mut::<AccumulatedMouseMotion>()
+            .delta = delta;
+        app.update();
+    }
+
+    fn click(app: &mut App) {
+        press(app);
+        release(app);
+    }
+
+    /// 本帧发出的消息数量
+    fn count<M: Message>(app: &App) -> usize {
+        app.world()
+            .resource::<Messages<M>>()
+            .iter_current_update_messages()
+            .count()
+    }
+
+    #[test]
+    fn short_press_is_a_click() {
+        let mut app = gesture_app();
+        press(&mut app);
+        assert_eq!(count::<GestureClick>(&app), 0);
+
+        release(&mut app);
+        assert_eq!(count::<GestureClick>(&app), 1);
+        assert_eq!(count::<GestureDragEnd>(&app), 0);
+        let position = app
+            .world()
+            .resource::<Messages<GestureClick>>()
+            .iter_current_update_messages()
+            .next()
+            .unwrap()
+            .position;
+        assert_eq!(position, Vec2::new(100.0, 100.0));
+    }
+
+    #[test]
+    fn movement_past_threshold_turns_press_into_drag() {
+        let mut app = gesture_app();
+        press(&mut app);
+
+        // 累计 3 像素，未超过 drag_distance
+        move_mouse(&mut app, Vec2::new(3.0, 0.0));
+        assert_eq!(count::<GestureDragStart>(&app), 0);
+        assert!(!app.world().resource::<Gestures>().is_dragging(BUTTON));
+
+        move_mouse(&mut app, Vec2::new(0.0, 4.0));
+        assert_eq!(count::<GestureDragStart>(&app), 1);
+        assert_eq!(count::<GestureDrag>(&app), 1);
+        assert!(app.world().resource::<Gestures>().is_dragging(BUTTON));
+
+        release(&mut app);
+        assert_eq!(count::<GestureDragEnd>(&app), 1);
+        assert_eq!(count::<GestureClick>(&app), 0);
+    }
+
+    #[test]
+    fn second_click_within_window_is_a_double_click() {
+        let mut app = gesture_app();
+        click(&mut app);
+        click(&mut app);
+        assert_eq!(count::<GestureClick>(&app), 1);
+        assert_eq!(count::<GestureDoubleClick>(&app), 1);
+
+        // 双击之后的下一次单击重新开始计算
+        click(&mut app);
+        assert_eq!(count::<GestureDoubleClick>(&app), 0);
+    }
+
+    #[test]
+    fn slow_second_click_is_not_a_double_click() {
+        let mut app = gesture_app();
+        click(&mut app);
+        for _ in 0..4 {
+            app.update();
+        }
+        click(&mut app);
+        assert_eq!(count::<GestureClick>(&app), 1);
+        assert_eq!(count::<GestureDoubleClick>(&app), 0);
+    }
+
+    #[test]
+    fn holding_still_is_a_long_press_not_a_click() {
+        let mut app = gesture_app();
+        press(&mut app);
+        app.update();
+        assert_eq!(count::<GestureLongPress>(&app), 0);
+
+        // 按住超过 long_press_time 后只发出一次
+        let mut long_presses = 0;
+        for _ in 0..4 {
+            app.update();
+            long_presses += count::<GestureLongPress>(&app);
+        }
+        assert_eq!(long_presses, 1);
+
+        release(&mut app);
+        assert_eq!(count::<GestureClick>(&app), 0);
+    }
+
+    #[test]
+    fn suspending_input_cancels_the_press() {
+        let mut app = gesture_app();
+        press(&mut app);
+        app.world_mut()
+            .resource_mut::<ActionState>()
+            .set_suspended(true);
+        app.update();
+        app.world_mut()
+            .resource_mut::<ActionState>()
+            .set_suspended(false);
+
+        release(&mut app);
+        assert_eq!(count::<GestureClick>(&app), 0);
+    }
+
+    #[test]
+    fn rebinding_cancels_the_press() {
+        let mut app = gesture_app();
+        press(&mut app);
+        *app.world_mut().resource_mut::<RebindState>() =
+            RebindState::Listening(tect_state::input::InputAction::Select);
+        release(&mut app);
+        assert_eq!(count::<GestureClick>(&app), 0);
+
+        *app.world_mut().resource_mut::<RebindState>() = RebindState::Idle;
+        click(&mut app);
+        assert_eq!(count::<GestureClick>(&app), 1);
+    }
+}
@@ -1 +1,10 @@
-pub mod moving;
\ No newline at end of file
+pub mod animation;
+pub mod click_effect;
+pub mod gesture;
+pub mod moving;
+pub mod navigation;
+pub mod orders;
+pub mod raycast;
+pub mod scene_mesh;
+pub mod selection;
+pub mod terrain;
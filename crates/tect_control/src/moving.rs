@@ -1,323 +1,228 @@
-use bevy::animation::{AnimationEvent, AnimationTargetId, RepeatAnimation};
-use bevy::asset::AssetContainer;
-use bevy::color::palettes::css::WHITE;
-use bevy::gltf::Gltf;
 ///外部使用改移动插件时在需要移动的组件生成时加上PlayerMove，地面组件加上Ground 并应用插件MoveControlPlugin
 use bevy::prelude::*;
-///描述：当前动画的加载与保存以及动画播放存在问题，与bevy0.17官方示例存在区别，且无法清除播放完的动画，动画事件未成功添加
-use std::time::Duration;
+use std::collections::VecDeque;
 use tect_state::app_state::*;
+use tect_state::input::{ActionState, InputAction, InputMap};
+
+use crate::animation::CharacterAnimationPlugin;
+use crate::click_effect::{ClickEffectPlugin, SpawnClickEffect};
+use crate::gesture::{GestureClick, GesturePlugin};
+use crate::navigation::{NavMesh, NavMeshPlugin};
+use crate::orders::{
+    command_selected_units_system, draw_order_paths, order_execution_system, CommandSelectedUnits,
+    OrderKind, OrderQueue,
+};
+use crate::raycast::{pointer_over_ui, GroundRaycast, MainCamera};
+use crate::selection::{Selected, SelectionPlugin};
+use crate::terrain::{terrain_follow_system, TerrainFollow};
 
 pub struct MoveControlPlugin;
 
 impl Plugin for MoveControlPlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<ParticleAssets>()
-            .add_observer(observe_on_click)
-            .add_systems(Startup, (setup, load_click_effect_assets))
-            .add_systems(
-                Update,
-                (
-                    mouse_button_system,
-                    character_movement_system,
-                    setup_click_effect_once_loaded,
-                    // setup_scene_once_loaded,
-                    despawn_finished_click_effects,
-                )
-                    .run_if(in_state(AppState::InGame))
-                    .chain(),
-            );
+        app.add_plugins((
+            NavMeshPlugin,
+            SelectionPlugin,
+            ClickEffectPlugin,
+            CharacterAnimationPlugin,
+            GesturePlugin,
+        ))
+        .add_message::<CommandSelectedUnits>()
+        .add_systems(
+            Update,
+            (
+                // 暂停时不接受新命令，移动本身随 Time<Virtual> 停止
+                mouse_button_system.run_if(in_state(InGameState::Playing)),
+                command_selected_units_system,
+                order_execution_system,
+                character_movement_system,
+                terrain_follow_system,
+                draw_order_paths,
+            )
+                .run_if(in_state(AppState::InGame))
+                .chain(),
+        );
     }
 }
 
 // 组件定义
 #[derive(Component)]
+#[require(TerrainFollow, OrderQueue)]
 pub struct PlayerMove {
+    /// 最大移动速度（米/秒）
     pub move_speed: f32,
     pub target_position: Option<Vec3>,
+    /// 寻路得到的路径点，依次前往，最后一个即 target_position
+    pub waypoints: VecDeque<Vec3>,
+    /// 转向速度（弧度/秒）
+    pub turn_rate: f32,
+    /// 加速度（米/秒²）
+    pub acceleration: f32,
+    /// 减速度（米/秒²），接近终点时按它提前减速
+    pub deceleration: f32,
+    /// 与路径点的距离小于该值即视为到达
+    pub stopping_radius: f32,
+    /// 当前速度（米/秒），由移动系统维护
+    pub current_speed: f32,
 }
 
-// ──────────────────────────────────────────────────────────────
-// 1. 资源定义：预加载的特效场景 + 动画图
-// ──────────────────────────────────────────────────────────────
-#[derive(Resource)]
-pub struct ClickEffectAssets {
-    pub scene: Handle<Scene>,
-    pub graph: Handle<AnimationGraph>,
-    pub click_animation: AnimationNodeIndex, // 我们只用一个“Click”动画
-    pub targt_id: AnimationTargetId,
+impl Default for PlayerMove {
+    fn default() -> Self {
+        Self {
+            move_speed: 2.0,
+            target_position: None,
+            waypoints: VecDeque::new(),
+            turn_rate: std::f32::consts::TAU,
+            acceleration: 8.0,
+            deceleration: 8.0,
+            stopping_radius: 0.1,
+            current_speed: 0.0,
+        }
+    }
 }
 
-// 资源：用于存储鼠标状态（现在部分状态由 RightMouseAction 管理）
-#[derive(Resource)]
-struct MouseState {
-    // is_right_clicked 和 right_click_position 不再用于判定，仅用于记录点击信息
-    is_right_clicked: bool,
-    target_is_reach: bool,
-    right_click_position: Vec2,
-    //鼠标样式动画
-    //TODO
+impl PlayerMove {
+    /// 设置新的目标点：有导航网格时沿 A* 路径绕开障碍，否则（或无路可达时）直线前往
+    pub fn set_destination(&mut self, from: Vec3, target: Vec3, navmesh: Option<&NavMesh>) {
+        let path = navmesh.and_then(|navmesh| navmesh.find_path(from, target));
+        self.waypoints = match path {
+            Some(path) => path.into(),
+            None => VecDeque::from([target]),
+        };
+        self.target_position = self.waypoints.back().copied();
+    }
+
+    /// 放弃当前目标，原地停下
+    pub fn stop(&mut self) {
+        self.waypoints.clear();
+        self.target_position = None;
+    }
 }
 
 #[derive(Component)]
 pub struct Ground;
 
-// 初始化资源
-fn setup(mut commands: Commands) {
-    // 初始化鼠标状态
-    commands.insert_resource(MouseState {
-        is_right_clicked: false,
-        target_is_reach: false,
-        right_click_position: Vec2::ZERO,
-    });
-}
-
 // 鼠标按键处理系统
+#[allow(clippy::too_many_arguments)]
 fn mouse_button_system(
-    mut mouse_state: ResMut<MouseState>,
-    mut right_mouse_action: ResMut<RightMouseAction>, // 共享状态
-    camera_query: Single<(&Camera, &GlobalTransform)>,
-    ground: Single<&GlobalTransform, With<Ground>>,
+    mut clicks: MessageReader<GestureClick>,
+    camera_query: Single<(&Camera, &GlobalTransform), With<MainCamera>>,
+    mut ground_raycast: GroundRaycast,
     window: Single<&Window>,
-    mut player_query: Query<(&mut Transform, &mut PlayerMove)>,
-    click_effect_assets: Res<ClickEffectAssets>,
-    mut commands: Commands,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    actions: Res<ActionState>,
+    input_map: Res<InputMap>,
+    interactions: Query<&Interaction>,
+    selected: Query<(), (With<Selected>, With<OrderQueue>)>,
+    mut unit_commands: MessageWriter<CommandSelectedUnits>,
+    mut click_effects: MessageWriter<SpawnClickEffect>,
 ) {
-    // 仅当 RightMouseAction 判定为 CharacterMove 时才执行移动逻辑
-    if *right_mouse_action != RightMouseAction::CharacterMove {
-        // 在这里，我们可以处理 CharacterMove 之后的重置，
-        // 或者简单地确保 CharacterMove 逻辑只运行一次。
+    // 鼠标绑定（默认右键）以单击手势触发，按住拖动留给相机旋转；
+    // 其余移动命令绑定（如手柄按键）按下即执行，以光标位置为目标
+    let is_command_button = |button: MouseButton| {
+        input_map
+            .mouse_buttons(InputAction::MoveCommand)
+            .any(|bound| bound == button)
+    };
+    let clicked = clicks
+        .read()
+        .filter(|click| is_command_button(click.button))
+        .last()
+        .map(|click| click.position);
+    let direct_command = actions.just_pressed(InputAction::MoveCommand)
+        && !mouse
+            .get_just_pressed()
+            .any(|button| is_command_button(*button))
+        && !pointer_over_ui(&interactions);
+    let Some(cursor_position) = clicked.or(window.cursor_position().filter(|_| direct_command))
+    else {
         return;
-    }
-
-    // 重置状态：一旦进入 CharacterMove 逻辑，无论是否找到目标，都意味着点击动作已处理
-    // 下一帧开始时，CameraControl 系统会再次设置 AwaitingDecision (如果右键仍按着)，或 None
-    *right_mouse_action = RightMouseAction::None;
+    };
 
-    // 以下是原有的移动逻辑，现在只在判定为 CharacterMove 时执行
     let (camera, camera_transform) = *camera_query;
 
-    if let Some(cursor_position) = window.cursor_position()
-        && let Ok(ray) = camera.viewport_to_world(camera_transform, cursor_position)
-        && let Some(distance) =
-            ray.intersect_plane(ground.translation(), InfinitePlane3d::new(ground.up()))
+    // 射线与 Ground 场景下的真实网格求交，得到命中点与表面法线
+    if let Some(hit) = ground_raycast.cast_from_viewport(camera, camera_transform, cursor_position)
     {
-        let point = ray.get_point(distance);
-        mouse_state.is_right_clicked = true;
-        mouse_state.right_click_position = cursor_position;
+        let point = hit.point;
 
-        //保存鼠标点击的目标地点
-        for (mut _transform, mut player) in player_query.iter_mut() {
-            let target_point = ray.origin + ray.direction * distance;
-            player.target_position = Some(target_point);
-            mouse_state.target_is_reach = false;
+        // 只有被选中的单位响应移动命令
+        if selected.is_empty() {
+            return;
         }
 
-        // —— 新增：生成外部动画特效 ——
-        spawn_click_effect(
-            &mut commands,
-            &click_effect_assets,
-            point,
-            ground.up().as_vec3(),
-        );
+        // 修饰键决定命令种类；按住 Shift 追加到命令队列，否则替换当前命令
+        unit_commands.write(CommandSelectedUnits {
+            target: point,
+            kind: OrderKind::from_modifiers(&keyboard),
+            append: keyboard.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]),
+        });
+
+        // —— 新增：生成外部动画特效，贴合命中表面 ——
+        click_effects.write(SpawnClickEffect {
+            position: point,
+            normal: hit.normal,
+        });
     }
-
-    // 释放逻辑：不再需要在这里处理 just_released，因为 CameraControl 已经通过 AwaitingDecision 状态处理了释放的判定。
-    // if mouse_button_input.just_released(MouseButton::Right) {
-    //     mouse_state.is_right_clicked = false;
-    // }
 }
 
 // 角色移动系统
 fn character_movement_system(
     mut player_query: Query<(&mut Transform, &mut PlayerMove)>,
-    mut mouse_state: ResMut<MouseState>,
     time: Res<Time>,
 ) {
-    if mouse_state.target_is_reach {
-        return;
-    };
-    //角色移动逻辑
+    //角色移动逻辑：沿路径点依次前进，平滑加减速与转向
+    let dt = time.delta_secs();
     for (mut transform, mut player) in player_query.iter_mut() {
-        // 如果已经设置了目标位置，则平滑移动过去
-        if let Some(target) = player.target_position {
-            let direction = target - transform.translation;
-            let distance = direction.length();
-
-            if distance > 0.1 {
-                let movement = direction.normalize() * player.move_speed * time.delta_secs();
-                // 让角色面向移动方向
-                let look_direction = Vec3::new(movement.x, 0.0, movement.z).normalize();
-                let translation = transform.translation;
-                transform.look_at(translation + look_direction, Vec3::Y);
-                // 只在XZ平面移动，保持Y坐标不变
-                transform.translation.x += movement.x;
-                transform.translation.z += movement.z;
-            } else {
-                mouse_state.target_is_reach = true;
-                player.target_position = None;
-            }
+        if player.target_position.is_none() {
+            player.current_speed = 0.0;
+            continue;
         }
-    }
-}
-
-///初始化右键动画资源
-pub fn load_click_effect_assets(
-    mut commands: Commands,
-    asset_server: Res<AssetServer>,
-    mut graphs: ResMut<Assets<AnimationGraph>>,
-) {
-    let scene_handle: Handle<Scene> =
-        asset_server.load(GltfAssetLabel::Scene(0).from_asset("rola/rola_run.glb"));
-
-    // 假设你的 glTF 中有一个名为 "Click" 的动画（索引 0）
-    let (graph, animation_indices) = AnimationGraph::from_clips([
-        asset_server.load(GltfAssetLabel::Animation(0).from_asset("rola/rola_run.glb"))
-    ]);
-    let graph_handle = graphs.add(graph);
-    let name = Name::new("on_click");
-
-    commands.insert_resource(ClickEffectAssets {
-        scene: scene_handle,
-        graph: graph_handle,
-        click_animation: animation_indices[0],
-        targt_id: AnimationTargetId::from_name(&name),
-    });
-}
-
-// ──────────────────────────────────────────────────────────────
-// 生成特效函数（在鼠标系统里调用）
-// ──────────────────────────────────────────────────────────────
-fn spawn_click_effect(
-    commands: &mut Commands,
-    effect_assets: &ClickEffectAssets,
-    position: Vec3,
-    ground_normal: Vec3,
-) {
-    commands.spawn((
-        SceneRoot(effect_assets.scene.clone()),
-        Transform::from_translation(position + ground_normal * 0.02)
-            .looking_to(ground_normal, Vec3::Y),
-        GlobalTransform::default(),
-        Visibility::Visible,
-        InheritedVisibility::default(),
-        ViewVisibility::default(),
-        ClickEffectMarker,
-        // 这些组件会在 setup_effect_once_loaded 中被填充
-        // 所以这里先占位，实际会在加载完成后插入
-    ));
-}
+        let Some(&waypoint) = player.waypoints.front() else {
+            player.target_position = None;
+            player.current_speed = 0.0;
+            continue;
+        };
 
-// 标记组件
-#[derive(Component)]
-struct ClickEffectMarker;
+        // 只在XZ平面移动，保持Y坐标不变
+        let direction = (waypoint - transform.translation).with_y(0.0);
+        let distance = direction.length();
 
-// ──────────────────────────────────────────────────────────────
-// 关键系统：场景加载完成后绑定动画图 + 播放一次 + 自动销毁
-// ──────────────────────────────────────────────────────────────
-fn setup_click_effect_once_loaded(
-    mut commands: Commands,
-    effect_assets: Res<ClickEffectAssets>,
-    animations: Res<ClickEffectAssets>,
-    graphs: Res<Assets<AnimationGraph>>,
-    mut clips: ResMut<Assets<AnimationClip>>,
-    // 只查询本次新生成的特效实体中，刚刚添加了 AnimationPlayer 的
-    mut query: Query<(Entity, &mut AnimationPlayer), Added<AnimationPlayer>>,
-) {
-    for (entity, mut player) in query.iter_mut() {
-        // 安全检查：确保是我们生成的特效
-        if commands.get_entity(entity).is_err() {
+        if distance <= player.stopping_radius {
+            // 到达当前路径点，切换到下一个；全部走完即到达目标
+            player.waypoints.pop_front();
+            if player.waypoints.is_empty() {
+                player.target_position = None;
+                player.current_speed = 0.0;
+            }
             continue;
         }
-        let graph = graphs.get(&animations.graph).unwrap();
-        let running_animation = get_clip(animations.click_animation, graph, &mut clips);
-
-        // You can determine the time an event should trigger if you know witch frame it occurs and
-        // the frame rate of the animation. Let's say we want to trigger an event at frame 15,
-        // and the animation has a frame rate of 24 fps, then time = 15 / 24 = 0.625.
-        running_animation.add_event_to_target(animations.targt_id, 0.625, OnClick);
 
-        let mut entity_cmds = commands.entity(entity);
-
-        // 插入动画图
-        entity_cmds.insert(AnimationGraphHandle(effect_assets.graph.clone()));
-
-        // 创建过渡控制器
-        let mut transitions = AnimationTransitions::new();
-
-        // 立即播放“Click”动画，0.2秒淡入，播放一次
-        transitions
-            .play(
-                player.as_mut(),
-                effect_assets.click_animation,
-                Duration::from_millis(0),
-            )
-            .set_repeat(RepeatAnimation::Count(1));
-
-        entity_cmds.insert(transitions);
-        // 可选：播放完后自动销毁（更稳妥的方式）
-        entity_cmds.insert(AutoDespawnOnAnimationFinish);
+        // 剩余路程：当前路径点之后的折线长度，据此在终点前提前减速
+        let remaining = distance
+            + player
+                .waypoints
+                .iter()
+                .zip(player.waypoints.iter().skip(1))
+                .map(|(a, b)| a.with_y(0.0).distance(b.with_y(0.0)))
+                .sum::<f32>();
+        let braking_speed =
+            (2.0 * player.deceleration * (remaining - player.stopping_radius).max(0.0)).sqrt();
+        let desired_speed = player.move_speed.min(braking_speed);
+        player.current_speed = if desired_speed > player.current_speed {
+            (player.current_speed + player.acceleration * dt).min(desired_speed)
+        } else {
+            (player.current_speed - player.deceleration * dt).max(desired_speed)
+        };
+
+        let step = (player.current_speed * dt).min(distance);
+        transform.translation += direction / distance * step;
+
+        // 按转向速度逐渐面向移动方向
+        let facing = Transform::IDENTITY.looking_to(direction, Vec3::Y).rotation;
+        transform.rotation = transform
+            .rotation
+            .rotate_towards(facing, player.turn_rate * dt);
     }
 }
-
-fn get_clip<'a>(
-    node: AnimationNodeIndex,
-    graph: &AnimationGraph,
-    clips: &'a mut Assets<AnimationClip>,
-) -> &'a mut AnimationClip {
-    let node = graph.get(node).unwrap();
-    let clip = match &node.node_type {
-        AnimationNodeType::Clip(handle) => clips.get_mut(handle),
-        _ => unreachable!(),
-    };
-    clip.unwrap()
-}
-
-// 标记：动画播放完后自动删除
-#[derive(Component)]
-struct AutoDespawnOnAnimationFinish;
-
-// ──────────────────────────────────────────────────────────────
-// 清理系统：监听动画结束事件并删除实体（官方推荐方式）
-// ──────────────────────────────────────────────────────────────
-fn despawn_finished_click_effects(
-    mut commands: Commands,
-    // mut click: On<OnClick>,
-    click: Query<Entity, With<AutoDespawnOnAnimationFinish>>,
-) {
-    // commands.entity().despawn();
-}
-
-#[derive(Resource)]
-struct ParticleAssets {
-    mesh: Handle<Mesh>,
-    material: Handle<StandardMaterial>,
-}
-
-impl FromWorld for ParticleAssets {
-    fn from_world(world: &mut World) -> Self {
-        Self {
-            mesh: world.add_asset::<Mesh>(Sphere::new(10.0)),
-            material: world.add_asset::<StandardMaterial>(StandardMaterial {
-                base_color: WHITE.into(),
-                ..Default::default()
-            }),
-        }
-    }
-}
-
-///鼠标右键动画事件
-#[derive(AnimationEvent, Reflect, Clone)]
-struct OnClick;
-
-fn observe_on_click(
-    step: On<OnClick>,
-    mut commands: Commands,
-    transforms: Query<&GlobalTransform>,
-) -> Result {
-    // let translation = transforms
-    //     .get(step.trigger().animation_player)?
-    //     .translation();
-    commands.entity(step.trigger().animation_player).despawn();
-    Ok(())
-}
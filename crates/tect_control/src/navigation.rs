@@ -0,0 +1,643 @@
+///导航网格：从带 Ground 标记的场景网格烘焙可行走高度场，提供 A* 寻路与路径平滑
+///烘焙方式参考 Recast 的第一步：把三角形光栅化到 XZ 网格，平缓的面作为地面，陡峭的面作为障碍，
+///再按角色半径向内收缩可行走区域，这样寻路结果就不会贴着墙角穿模
+use bevy::prelude::*;
+use bevy::scene::SceneInstanceReady;
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, VecDeque};
+use std::time::Instant;
+use tect_state::app_state::*;
+
+use crate::moving::Ground;
+use crate::scene_mesh::SceneMeshes;
+
+pub struct NavMeshPlugin;
+
+impl Plugin for NavMeshPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<NavMeshSettings>()
+            .add_observer(bake_navmesh_on_ground_ready)
+            .add_systems(OnExit(AppState::InGame), remove_navmesh);
+    }
+}
+
+// ──────────────────────────────────────────────────────────────
+// 1. 烘焙参数
+// ──────────────────────────────────────────────────────────────
+#[derive(Resource, Debug, Clone)]
+pub struct NavMeshSettings {
+    /// 网格单元边长（米），越小越精细，烘焙与寻路也越慢
+    pub cell_size: f32,
+    /// 角色半径，可行走区域会按此半径从障碍物边缘向内收缩
+    pub agent_radius: f32,
+    /// 角色高度，障碍物低于地面 + 该高度时才会阻挡
+    pub agent_height: f32,
+    /// 最大可行走坡度（弧度）
+    pub max_slope: f32,
+    /// 相邻单元允许跨越的最大台阶高度
+    pub max_step_height: f32,
+}
+
+impl Default for NavMeshSettings {
+    fn default() -> Self {
+        Self {
+            cell_size: 0.5,
+            agent_radius: 0.4,
+            agent_height: 1.8,
+            max_slope: std::f32::consts::FRAC_PI_4,
+            max_step_height: 0.4,
+        }
+    }
+}
+
+// ──────────────────────────────────────────────────────────────
+// 2. 导航网格资源
+// ──────────────────────────────────────────────────────────────
+/// 烘焙好的可行走高度场，每个单元记录地面高度，`None` 表示不可行走
+#[derive(Resource, Debug, Clone)]
+pub struct NavMesh {
+    /// 网格最小角在 XZ 平面上的坐标
+    origin: Vec2,
+    cell_size: f32,
+    width: usize,
+    depth: usize,
+    max_step_height: f32,
+    cells: Vec<Option<f32>>,
+}
+
+// 8 邻域偏移（前 4 个为直线方向）
+const NEIGHBORS: [(i32, i32); 8] = [
+    (1, 0),
+    (-1, 0),
+    (0, 1),
+    (0, -1),
+    (1, 1),
+    (1, -1),
+    (-1, 1),
+    (-1, -1),
+];
+
+impl NavMesh {
+    /// 从世界空间三角形烘焙导航网格，没有任何几何体时返回 None
+    pub fn bake(triangles: &[[Vec3; 3]], settings: &NavMeshSettings) -> Option<Self> {
+        if triangles.is_empty() || settings.cell_size <= 0.0 {
+            return None;
+        }
+
+        let mut min = Vec2::splat(f32::MAX);
+        let mut max = Vec2::splat(f32::MIN);
+        for vertex in triangles.iter().flatten() {
+            min = min.min(vertex.xz());
+            max = max.max(vertex.xz());
+        }
+
+        let cell_size = settings.cell_size;
+        let width = (((max.x - min.x) / cell_size).ceil() as usize).max(1);
+        let depth = (((max.y - min.y) / cell_size).ceil() as usize).max(1);
+        let mut navmesh = Self {
+            origin: min,
+            cell_size,
+            width,
+            depth,
+            max_step_height: settings.max_step_height,
+            cells: vec![None; width * depth],
+        };
+
+        let min_normal_y = settings.max_slope.cos();
+        let mut steep = Vec::new();
+
+        // 第一遍：平缓的面写入地面高度（同一单元取最高的面）
+        for triangle in triangles {
+            let normal = (triangle[1] - triangle[0]).cross(triangle[2] - triangle[0]);
+            let Some(normal) = normal.try_normalize() else {
+                // 退化三角形（例如竖直的细长面在 XZ 上的投影）按障碍处理
+                steep.push(triangle);
+                continue;
+            };
+            if normal.y.abs() < min_normal_y {
+                steep.push(triangle);
+                continue;
+            }
+            navmesh.rasterize(triangle, |cells, index, height| {
+                if let Some(height) = height
+                    && cells[index].is_none_or(|current| height > current)
+                {
+                    cells[index] = Some(height);
+                }
+            });
+        }
+
+        // 第二遍：陡峭的面如果挡在地面以上、角色高度以内，则该单元不可行走
+        for triangle in steep {
+            let low = triangle[0].y.min(triangle[1].y).min(triangle[2].y);
+            let high = triangle[0].y.max(triangle[1].y).max(triangle[2].y);
+            navmesh.rasterize(triangle, |cells, index, _| {
+                if let Some(floor) = cells[index]
+                    && high > floor + settings.max_step_height
+                    && low < floor + settings.agent_height
+                {
+                    cells[index] = None;
+                }
+            });
+        }
+
+        navmesh.erode(settings.agent_radius);
+        Some(navmesh)
+    }
+
+    /// 查询从 `start` 到 `goal` 的路径，返回平滑后的路径点（不含起点，最后一个为终点）
+    /// 起点或终点落在不可行走区域时会吸附到最近的可行走单元
+    pub fn find_path(&self, start: Vec3, goal: Vec3) -> Option<Vec<Vec3>> {
+        let start_cell = self.nearest_walkable(start.xz())?;
+        let goal_cell = self.nearest_walkable(goal.xz())?;
+
+        let cells = self.a_star(start_cell, goal_cell)?;
+        let cells = self.smooth(&cells);
+
+        let mut path: Vec<Vec3> = cells.iter().skip(1).map(|&c| self.cell_center(c)).collect();
+        // 终点单元可行走时保留精确的点击位置，而不是单元中心
+        let end = if self.index_of(goal.xz()) == Some(goal_cell) {
+            goal.with_y(self.cells[goal_cell].unwrap_or(goal.y))
+        } else {
+            self.cell_center(goal_cell)
+        };
+        match path.last_mut() {
+            Some(last) => *last = end,
+            None => path.push(end),
+        }
+        Some(path)
+    }
+
+    /// 查询某点所在单元的地面高度，不可行走或超出范围时返回 None
+    pub fn height_at(&self, position: Vec3) -> Option<f32> {
+        self.index_of(position.xz()).and_then(|i| self.cells[i])
+    }
+
+    /// 某点是否位于可行走区域
+    pub fn is_walkable(&self, position: Vec3) -> bool {
+        self.height_at(position).is_some()
+    }
+
+    fn index_of(&self, point: Vec2) -> Option<usize> {
+        let local = (point - self.origin) / self.cell_size;
+        if local.x < 0.0 || local.y < 0.0 {
+            return None;
+        }
+        let (x, z) = (local.x as usize, local.y as usize);
+        (x < self.width && z < self.depth).then(|| z * self.width + x)
+    }
+
+    fn coords(&self, index: usize) -> (i32, i32) {
+        ((index % self.width) as i32, (index / self.width) as i32)
+    }
+
+    fn index_at(&self, x: i32, z: i32) -> Option<usize> {
+        (x >= 0 && z >= 0 && (x as usize) < self.width && (z as usize) < self.depth)
+            .then(|| z as usize * self.width + x as usize)
+    }
+
+    fn cell_center(&self, index: usize) -> Vec3 {
+        let (x, z) = self.coords(index);
+        let xz = self.origin + (Vec2::new(x as f32, z as f32) + 0.5) * self.cell_size;
+        Vec3::new(xz.x, self.cells[index].unwrap_or(0.0), xz.y)
+    }
+
+    /// 两个相邻单元之间能否通行（都可行走且高度差不超过台阶高度）
+    fn can_step(&self, from: usize, to: usize) -> bool {
+        match (self.cells[from], self.cells[to]) {
+            (Some(a), Some(b)) => (a - b).abs() <= self.max_step_height,
+            _ => false,
+        }
+    }
+
+    /// 把三角形光栅化到网格：对单元中心落在三角形投影内的单元给出插值高度，
+    /// 对三角形边经过的单元给出 None（用于保守地覆盖竖直面和细长面）
+    fn rasterize(
+        &mut self,
+        triangle: &[Vec3; 3],
+        mut write: impl FnMut(&mut [Option<f32>], usize, Option<f32>),
+    ) {
+        let [a, b, c] = triangle.map(|v| v.xz());
+        let min = a.min(b).min(c);
+        let max = a.max(b).max(c);
+        let to_cell = |p: Vec2| ((p - self.origin) / self.cell_size).floor();
+        let lo = to_cell(min).max(Vec2::ZERO);
+        let hi = to_cell(max).min(Vec2::new(self.width as f32 - 1.0, self.depth as f32 - 1.0));
+
+        let area = (b - a).perp_dot(c - a);
+        if area.abs() > f32::EPSILON {
+            for z in lo.y as usize..=hi.y as usize {
+                for x in lo.x as usize..=hi.x as usize {
+                    let p = self.origin + (Vec2::new(x as f32, z as f32) + 0.5) * self.cell_size;
+                    let w0 = (c - b).perp_dot(p - b) / area;
+                    let w1 = (a - c).perp_dot(p - c) / area;
+                    let w2 = 1.0 - w0 - w1;
+                    if w0 >= 0.0 && w1 >= 0.0 && w2 >= 0.0 {
+                        let height = w0 * triangle[0].y + w1 * triangle[1].y + w2 * triangle[2].y;
+                        write(&mut self.cells, z * self.width + x, Some(height));
+                    }
+                }
+            }
+        }
+
+        // 沿三条边按半个单元步进，补上投影面积很小的三角形
+        for (from, to) in [(a, b), (b, c), (c, a)] {
+            let steps = ((to - from).length() / (self.cell_size * 0.5))
+                .ceil()
+                .max(1.0) as usize;
+            for step in 0..=steps {
+                let p = from.lerp(to, step as f32 / steps as f32);
+                if let Some(index) = self.index_of(p) {
+                    write(&mut self.cells, index, None);
+                }
+            }
+        }
+    }
+
+    /// 按角色半径收缩可行走区域：离障碍、地图边界或落差边缘过近的单元都标记为不可行走
+    fn erode(&mut self, radius: f32) {
+        let radius_cells = (radius / self.cell_size).ceil() as u32;
+        if radius_cells == 0 {
+            return;
+        }
+
+        let mut distance = vec![u32::MAX; self.cells.len()];
+        let mut queue = VecDeque::new();
+        for (index, cell_distance) in distance.iter_mut().enumerate() {
+            let (x, z) = self.coords(index);
+            let is_edge = self.cells[index].is_none()
+                || NEIGHBORS[..4].iter().any(|&(dx, dz)| {
+                    self.index_at(x + dx, z + dz)
+                        .is_none_or(|neighbor| !self.can_step(index, neighbor))
+                });
+            if is_edge {
+                *cell_distance = 0;
+                queue.push_back(index);
+            }
+        }
+
+        while let Some(index) = queue.pop_front() {
+            let next = distance[index] + 1;
+            if next > radius_cells {
+                continue;
+            }
+            let (x, z) = self.coords(index);
+            for (dx, dz) in NEIGHBORS {
+                if let Some(neighbor) = self.index_at(x + dx, z + dz)
+                    && distance[neighbor] > next
+                {
+                    distance[neighbor] = next;
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+
+        for (cell, distance) in self.cells.iter_mut().zip(distance) {
+            if distance < radius_cells {
+                *cell = None;
+            }
+        }
+    }
+
+    /// 以螺旋方式查找离某点最近的可行走单元
+    fn nearest_walkable(&self, point: Vec2) -> Option<usize> {
+        let local = ((point - self.origin) / self.cell_size).floor();
+        let (cx, cz) = (local.x as i32, local.y as i32);
+        let max_ring = self.width.max(self.depth) as i32;
+        for ring in 0..=max_ring {
+            let mut best: Option<(f32, usize)> = None;
+            for dz in -ring..=ring {
+                for dx in -ring..=ring {
+                    if dx.abs() != ring && dz.abs() != ring {
+                        continue;
+                    }
+                    let Some(index) = self.index_at(cx + dx, cz + dz) else {
+                        continue;
+                    };
+                    if self.cells[index].is_some() {
+                        let distance = self.cell_center(index).xz().distance_squared(point);
+                        if best.is_none_or(|(d, _)| distance < d) {
+                            best = Some((distance, index));
+                        }
+                    }
+                }
+            }
+            if let Some((_, index)) = best {
+                return Some(index);
+            }
+        }
+        None
+    }
+
+    /// 8 邻域 A*，对角移动不允许切墙角
+    fn a_star(&self, start: usize, goal: usize) -> Option<Vec<usize>> {
+        let heuristic = |index: usize| {
+            let (x, z) = self.coords(index);
+            let (gx, gz) = self.coords(goal);
+            let (dx, dz) = ((x - gx).abs() as f32, (z - gz).abs() as f32);
+            dx.max(dz) + (std::f32::consts::SQRT_2 - 1.0) * dx.min(dz)
+        };
+
+        let mut cost = vec![f32::INFINITY; self.cells.len()];
+        let mut came_from = vec![usize::MAX; self.cells.len()];
+        let mut open = BinaryHeap::new();
+        cost[start] = 0.0;
+        open.push(OpenNode {
+            score: heuristic(start),
+            index: start,
+        });
+
+        while let Some(OpenNode { score, index }) = open.pop() {
+            if index == goal {
+                let mut path = vec![goal];
+                let mut current = goal;
+                while current != start {
+                    current = came_from[current];
+                    path.push(current);
+                }
+                path.reverse();
+                return Some(path);
+            }
+            // 堆中的过期节点
+            if score > cost[index] + heuristic(index) + f32::EPSILON {
+                continue;
+            }
+
+            let (x, z) = self.coords(index);
+            for (dx, dz) in NEIGHBORS {
+                let Some(neighbor) = self.index_at(x + dx, z + dz) else {
+                    continue;
+                };
+                if !self.can_step(index, neighbor) {
+                    continue;
+                }
+                let diagonal = dx != 0 && dz != 0;
+                if diagonal {
+                    let side_a = self.index_at(x + dx, z);
+                    let side_b = self.index_at(x, z + dz);
+                    let clear = |side: Option<usize>| side.is_some_and(|s| self.can_step(index, s));
+                    if !clear(side_a) || !clear(side_b) {
+                        continue;
+                    }
+                }
+                let step = if diagonal {
+                    std::f32::consts::SQRT_2
+                } else {
+                    1.0
+                };
+                let new_cost = cost[index] + step;
+                if new_cost < cost[neighbor] {
+                    cost[neighbor] = new_cost;
+                    came_from[neighbor] = index;
+                    open.push(OpenNode {
+                        score: new_cost + heuristic(neighbor),
+                        index: neighbor,
+                    });
+                }
+            }
+        }
+        None
+    }
+
+    /// 拉绳式路径平滑：从当前锚点尽量连到最远的可直达单元，去掉锯齿状的中间点
+    fn smooth(&self, cells: &[usize]) -> Vec<usize> {
+        let Some(&first) = cells.first() else {
+            return Vec::new();
+        };
+        let mut result = vec![first];
+        let mut anchor = 0;
+        while anchor < cells.len() - 1 {
+            let mut next = anchor + 1;
+            for candidate in (anchor + 2..cells.len()).rev() {
+                if self.line_of_sight(cells[anchor], cells[candidate]) {
+                    next = candidate;
+                    break;
+                }
+            }
+            result.push(cells[next]);
+            anchor = next;
+        }
+        result
+    }
+
+    /// 两个单元中心之间的直线是否只经过可通行的单元
+    fn line_of_sight(&self, from: usize, to: usize) -> bool {
+        let a = self.cell_center(from).xz();
+        let b = self.cell_center(to).xz();
+        let steps = ((b - a).length() / (self.cell_size * 0.25)).ceil().max(1.0) as usize;
+        let mut previous = from;
+        for step in 1..=steps {
+            let Some(index) = self.index_of(a.lerp(b, step as f32 / steps as f32)) else {
+                return false;
+            };
+            if index != previous {
+                if !self.can_step(previous, index) {
+                    return false;
+                }
+                previous = index;
+            }
+        }
+        true
+    }
+}
+
+/// A* 开放列表中的节点，按 score 从小到大出堆
+#[derive(Debug, Clone, Copy)]
+struct OpenNode {
+    score: f32,
+    index: usize,
+}
+
+impl PartialEq for OpenNode {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for OpenNode {}
+
+impl PartialOrd for OpenNode {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for OpenNode {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.score.total_cmp(&self.score)
+    }
+}
+
+// ──────────────────────────────────────────────────────────────
+// 3. 地面场景加载完成后烘焙
+// ──────────────────────────────────────────────────────────────
+/// Ground 场景实例化完成时收集其下所有网格并烘焙导航网格
+fn bake_navmesh_on_ground_ready(
+    ready: On<SceneInstanceReady>,
+    mut commands: Commands,
+    grounds: Query<(), With<Ground>>,
+    scene_meshes: SceneMeshes,
+    settings: Res<NavMeshSettings>,
+) {
+    if !grounds.contains(ready.entity) {
+        return;
+    }
+
+    let started = Instant::now();
+    let triangles = scene_meshes.triangles(ready.entity);
+
+    match NavMesh::bake(&triangles, &settings) {
+        Some(navmesh) => {
+            info!(
+                "导航网格烘焙完成：{} 个三角形，{}x{} 单元，耗时 {:?}",
+                triangles.len(),
+                navmesh.width,
+                navmesh.depth,
+                started.elapsed()
+            );
+            commands.insert_resource(navmesh);
+        }
+        None => warn!("Ground 场景中没有可用于烘焙导航网格的三角形"),
+    }
+}
+
+// ──────────────────────────────────────────────────────────────
+// 4. 离开游戏时移除
+// ──────────────────────────────────────────────────────────────
+/// 导航网格属于当前地图，离开游戏时移除，下一局的地面场景加载完成后重新烘焙
+fn remove_navmesh(mut commands: Commands) {
+    commands.remove_resource::<NavMesh>();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 10x10 的水平地面，位于 XZ 平面 (0,0)~(10,10)
+    fn floor() -> Vec<[Vec3; 3]> {
+        vec![
+            [
+                Vec3::new(0.0, 0.0, 0.0),
+                Vec3::new(10.0, 0.0, 0.0),
+                Vec3::new(10.0, 0.0, 10.0),
+            ],
+            [
+                Vec3::new(0.0, 0.0, 0.0),
+                Vec3::new(10.0, 0.0, 10.0),
+                Vec3::new(0.0, 0.0, 10.0),
+            ],
+        ]
+    }
+
+    /// x = 5 处的竖直墙面，沿 Z 从 z0 延伸到 z1，高 2 米
+    fn wall(z0: f32, z1: f32) -> Vec<[Vec3; 3]> {
+        vec![
+            [
+                Vec3::new(5.0, 0.0, z0),
+                Vec3::new(5.0, 2.0, z0),
+                Vec3::new(5.0, 2.0, z1),
+            ],
+            [
+                Vec3::new(5.0, 0.0, z0),
+                Vec3::new(5.0, 2.0, z1),
+                Vec3::new(5.0, 0.0, z1),
+            ],
+        ]
+    }
+
+    fn no_radius() -> NavMeshSettings {
+        NavMeshSettings {
+            agent_radius: 0.0,
+            ..default()
+        }
+    }
+
+    #[test]
+    fn bake_without_triangles_returns_none() {
+        assert!(NavMesh::bake(&[], &NavMeshSettings::default()).is_none());
+    }
+
+    #[test]
+    fn bake_flat_floor_is_walkable() {
+        let navmesh = NavMesh::bake(&floor(), &no_radius()).unwrap();
+        assert_eq!((navmesh.width, navmesh.depth), (20, 20));
+        assert!(navmesh.cells.iter().all(Option::is_some));
+        assert_eq!(navmesh.height_at(Vec3::new(5.0, 3.0, 5.0)), Some(0.0));
+        assert_eq!(navmesh.height_at(Vec3::new(-1.0, 0.0, 5.0)), None);
+    }
+
+    #[test]
+    fn bake_marks_wall_cells_blocked() {
+        let mut triangles = floor();
+        triangles.extend(wall(0.0, 7.0));
+        let navmesh = NavMesh::bake(&triangles, &no_radius()).unwrap();
+
+        assert!(!navmesh.is_walkable(Vec3::new(5.1, 0.0, 3.0)));
+        assert!(navmesh.is_walkable(Vec3::new(5.6, 0.0, 3.0)));
+        // 墙的尽头之后可以通行
+        assert!(navmesh.is_walkable(Vec3::new(5.1, 0.0, 8.5)));
+    }
+
+    #[test]
+    fn erosion_keeps_agent_away_from_obstacles_and_edges() {
+        let mut triangles = floor();
+        triangles.extend(wall(0.0, 7.0));
+        let navmesh = NavMesh::bake(&triangles, &NavMeshSettings::default()).unwrap();
+
+        // 紧贴墙面与地图边界的单元被收缩掉
+        assert!(!navmesh.is_walkable(Vec3::new(5.6, 0.0, 3.0)));
+        assert!(!navmesh.is_walkable(Vec3::new(4.6, 0.0, 3.0)));
+        assert!(!navmesh.is_walkable(Vec3::new(0.2, 0.0, 5.0)));
+        // 再往外一格恢复可行走
+        assert!(navmesh.is_walkable(Vec3::new(6.1, 0.0, 3.0)));
+        assert!(navmesh.is_walkable(Vec3::new(0.7, 0.0, 5.0)));
+    }
+
+    #[test]
+    fn find_path_on_open_floor_goes_straight() {
+        let navmesh = NavMesh::bake(&floor(), &NavMeshSettings::default()).unwrap();
+        let goal = Vec3::new(8.2, 0.0, 7.9);
+        let path = navmesh.find_path(Vec3::new(2.0, 0.0, 2.0), goal).unwrap();
+        assert_eq!(path, vec![goal]);
+    }
+
+    #[test]
+    fn find_path_goes_around_wall() {
+        let mut triangles = floor();
+        triangles.extend(wall(0.0, 7.0));
+        let navmesh = NavMesh::bake(&triangles, &NavMeshSettings::default()).unwrap();
+
+        let goal = Vec3::new(8.0, 0.0, 2.0);
+        let path = navmesh.find_path(Vec3::new(2.0, 0.0, 2.0), goal).unwrap();
+        assert_eq!(path.last(), Some(&goal));
+        assert!(path.len() > 1);
+        // 必须绕过墙的尽头
+        assert!(path.iter().any(|point| point.z > 7.0));
+        assert!(path.iter().all(|point| navmesh.is_walkable(*point)));
+    }
+
+    #[test]
+    fn find_path_snaps_blocked_goal_to_nearest_walkable_cell() {
+        let mut triangles = floor();
+        triangles.extend(wall(0.0, 7.0));
+        let navmesh = NavMesh::bake(&triangles, &NavMeshSettings::default()).unwrap();
+
+        let path = navmesh
+            .find_path(Vec3::new(2.0, 0.0, 2.0), Vec3::new(5.0, 0.0, 3.0))
+            .unwrap();
+        let end = *path.last().unwrap();
+        assert!(navmesh.is_walkable(end));
+        assert!(end.x < 5.0);
+    }
+
+    #[test]
+    fn find_path_returns_none_when_blocked() {
+        let mut triangles = floor();
+        triangles.extend(wall(-1.0, 11.0));
+        let navmesh = NavMesh::bake(&triangles, &NavMeshSettings::default()).unwrap();
+
+        assert!(navmesh
+            .find_path(Vec3::new(2.0, 0.0, 2.0), Vec3::new(8.0, 0.0, 2.0))
+            .is_none());
+    }
+}
@@ -0,0 +1,314 @@
+///单位命令队列：右键下达命令，按住 Shift 追加到队尾而不是替换当前命令
+///命令类型：移动、攻击移动（Ctrl + 右键）、巡逻（Alt + 右键），被选中单位的命令路线会画在地面上
+///攻击移动途中有 Hostile 单位进入范围时停下，敌人离开范围后继续前进
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+use crate::moving::PlayerMove;
+use crate::navigation::NavMesh;
+use crate::selection::{assign_formation, Selected, FORMATION_SPACING};
+use crate::terrain::{GroundContact, TerrainFollow};
+
+// 路线离地高度，避免与地面 Z-fighting
+const PATH_LINE_LIFT: f32 = 0.05;
+/// 攻击移动的索敌范围（米，XZ 平面距离）
+pub const ATTACK_MOVE_RANGE: f32 = 6.0;
+
+/// 敌方单位，攻击移动中的单位在其进入 ATTACK_MOVE_RANGE 时停下
+#[derive(Component, Debug, Default)]
+pub struct Hostile;
+
+/// 单个命令
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Order {
+    /// 移动到目标点
+    Move(Vec3),
+    /// 向目标点移动，途中有敌人进入索敌范围时原地停下，敌人离开范围后继续前进
+    AttackMove(Vec3),
+    /// 在命令开始时单位脚下的地面点与目标点之间往返，不会自行结束
+    Patrol { from: Option<Vec3>, to: Vec3 },
+}
+
+impl Order {
+    pub fn patrol(to: Vec3) -> Self {
+        Order::Patrol { from: None, to }
+    }
+
+    /// 当前要前往的目标点
+    pub fn target(&self) -> Vec3 {
+        match *self {
+            Order::Move(target) | Order::AttackMove(target) => target,
+            Order::Patrol { to, .. } => to,
+        }
+    }
+
+    fn color(&self) -> Color {
+        match self {
+            Order::Move(_) => Color::srgb(0.3, 1.0, 0.4),
+            Order::AttackMove(_) => Color::srgb(1.0, 0.3, 0.25),
+            Order::Patrol { .. } => Color::srgb(1.0, 0.85, 0.2),
+        }
+    }
+}
+
+/// 命令的种类，由下达命令时按住的修饰键决定
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OrderKind {
+    #[default]
+    Move,
+    AttackMove,
+    Patrol,
+}
+
+impl OrderKind {
+    /// 根据当前按住的修饰键判断命令种类
+    pub fn from_modifiers(keyboard: &ButtonInput<KeyCode>) -> Self {
+        if keyboard.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]) {
+            OrderKind::AttackMove
+        } else if keyboard.any_pressed([KeyCode::AltLeft, KeyCode::AltRight]) {
+            OrderKind::Patrol
+        } else {
+            OrderKind::Move
+        }
+    }
+
+    pub fn order(self, target: Vec3) -> Order {
+        match self {
+            OrderKind::Move => Order::Move(target),
+            OrderKind::AttackMove => Order::AttackMove(target),
+            OrderKind::Patrol => Order::patrol(target),
+        }
+    }
+}
+
+/// 向所有选中单位下达命令，多个单位按方阵分散到目标点周围
+#[derive(Message, Debug, Clone, Copy)]
+pub struct CommandSelectedUnits {
+    pub target: Vec3,
+    pub kind: OrderKind,
+    /// 追加到命令队列而不是替换当前命令
+    pub append: bool,
+}
+
+/// 单位的命令队列，队首为正在执行的命令；随单位一起存档
+#[derive(Component, Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct OrderQueue {
+    orders: VecDeque<Order>,
+    /// 队首命令是否已经开始执行（已规划路径）
+    started: bool,
+}
+
+impl OrderQueue {
+    /// 清空队列并立即执行新命令
+    pub fn replace(&mut self, order: Order) {
+        self.orders.clear();
+        self.orders.push_back(order);
+        self.started = false;
+    }
+
+    /// 追加到队尾，前面的命令完成后执行
+    pub fn push(&mut self, order: Order) {
+        self.orders.push_back(order);
+    }
+
+    pub fn clear(&mut self) {
+        self.orders.clear();
+        self.started = false;
+    }
+
+    /// 正在执行的命令
+    pub fn current(&self) -> Option<&Order> {
+        self.orders.front()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Order> {
+        self.orders.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.orders.is_empty()
+    }
+}
+
+/// 把命令分配给选中单位；追加命令时编队以各单位队尾命令的目标点为起点排布
+pub(crate) fn command_selected_units_system(
+    mut requests: MessageReader<CommandSelectedUnits>,
+    mut units: Query<(Entity, &Transform, &mut OrderQueue), With<Selected>>,
+) {
+    for request in requests.read() {
+        let positions: Vec<(Entity, Vec3)> = units
+            .iter()
+            .map(|(entity, transform, orders)| {
+                let start = match orders.iter().last() {
+                    Some(last) if request.append => last.target(),
+                    _ => transform.translation,
+                };
+                (entity, start)
+            })
+            .collect();
+        for (entity, slot) in assign_formation(&positions, request.target, FORMATION_SPACING) {
+            if let Ok((_, _, mut orders)) = units.get_mut(entity) {
+                if request.append {
+                    orders.push(request.kind.order(slot));
+                } else {
+                    orders.replace(request.kind.order(slot));
+                }
+            }
+        }
+    }
+}
+
+/// 推进命令队列：开始队首命令，移动结束后切换到下一条，巡逻命令到达后折返，
+/// 攻击移动遇敌时停下
+#[allow(clippy::type_complexity)]
+pub(crate) fn order_execution_system(
+    mut units: Query<(
+        Entity,
+        &Transform,
+        &mut PlayerMove,
+        &mut OrderQueue,
+        Option<&TerrainFollow>,
+        Option<&GroundContact>,
+    )>,
+    hostiles: Query<(Entity, &Transform), With<Hostile>>,
+    navmesh: Option<Res<NavMesh>>,
+) {
+    for (entity, transform, mut player, mut queue, follow, contact) in units.iter_mut() {
+        let position = transform.translation;
+
+        if let Some(Order::AttackMove(_)) = queue.current()
+            && hostile_in_range(entity, position, &hostiles)
+        {
+            // 敌人进入范围：原地停下交战，标记为未开始以便敌人离开后重新规划路径
+            player.stop();
+            queue.started = false;
+            continue;
+        }
+
+        if queue.started {
+            // 仍在前往当前命令的目标
+            if player.target_position.is_some() {
+                continue;
+            }
+            match queue.orders.front_mut() {
+                Some(Order::Patrol { from, to }) => {
+                    // 巡逻：到达一端后折返
+                    let back = from.unwrap_or_else(|| ground_point(position, follow, contact));
+                    *from = Some(*to);
+                    *to = back;
+                    let target = *to;
+                    player.set_destination(position, target, navmesh.as_deref());
+                    continue;
+                }
+                _ => {
+                    queue.orders.pop_front();
+                    queue.started = false;
+                }
+            }
+        }
+
+        let Some(order) = queue.orders.front_mut() else {
+            continue;
+        };
+        if let Order::Patrol { from, .. } = order {
+            // 巡逻起点取脚下的地面点，与其余路线一样贴地绘制
+            from.get_or_insert_with(|| ground_point(position, follow, contact));
+        }
+        let target = order.target();
+        player.set_destination(position, target, navmesh.as_deref());
+        queue.started = true;
+    }
+}
+
+/// 单位脚下的地面点：优先使用地形跟随的采样高度，没有采样时减去模型原点的高度
+fn ground_point(
+    position: Vec3,
+    follow: Option<&TerrainFollow>,
+    contact: Option<&GroundContact>,
+) -> Vec3 {
+    match contact {
+        Some(contact) if contact.grounded => position.with_y(contact.ground_height),
+        _ => position - Vec3::Y * follow.map_or(0.0, |follow| follow.height_offset),
+    }
+}
+
+fn hostile_in_range(
+    unit: Entity,
+    position: Vec3,
+    hostiles: &Query<(Entity, &Transform), With<Hostile>>,
+) -> bool {
+    hostiles.iter().any(|(hostile, transform)| {
+        hostile != unit
+            && transform.translation.xz().distance_squared(position.xz())
+                <= ATTACK_MOVE_RANGE * ATTACK_MOVE_RANGE
+    })
+}
+
+/// 在地面上绘制被选中单位的剩余路线与排队中的命令点
+pub(crate) fn draw_order_paths(
+    mut gizmos: Gizmos,
+    units: Query<(&Transform, &PlayerMove, &OrderQueue, Option<&TerrainFollow>), With<Selected>>,
+) {
+    let lift = Vec3::Y * PATH_LINE_LIFT;
+    for (transform, player, queue, follow) in units.iter() {
+        let Some(current) = queue.current() else {
+            continue;
+        };
+        let height_offset = follow.map_or(0.0, |f| f.height_offset);
+        let feet = transform.translation - Vec3::Y * height_offset;
+
+        // 当前命令：沿寻路结果画线
+        let mut points = vec![feet + lift];
+        points.extend(player.waypoints.iter().map(|p| *p + lift));
+        gizmos.linestrip(points, current.color());
+
+        // 排队中的命令：从上一个目标点直线连到下一个
+        let mut previous = current.target();
+        if let Order::Patrol {
+            from: Some(from), ..
+        } = current
+        {
+            gizmos.line(previous + lift, *from + lift, current.color());
+        }
+        for order in queue.iter().skip(1) {
+            let target = order.target();
+            gizmos.line(previous + lift, target + lift, order.color());
+            gizmos.sphere(
+                Isometry3d::from_translation(target + lift),
+                0.15,
+                order.color(),
+            );
+            previous = target;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ground_point_drops_height_offset() {
+        let position = Vec3::new(2.0, 1.6, -3.0);
+        let follow = TerrainFollow {
+            height_offset: 1.0,
+            ..default()
+        };
+        let grounded = GroundContact {
+            grounded: true,
+            ground_height: 0.5,
+            ..default()
+        };
+
+        assert_eq!(
+            ground_point(position, Some(&follow), Some(&grounded)),
+            Vec3::new(2.0, 0.5, -3.0)
+        );
+        // 还没有采样到地面时按模型原点高度估算
+        let airborne = GroundContact::default();
+        assert!(ground_point(position, Some(&follow), Some(&airborne))
+            .abs_diff_eq(Vec3::new(2.0, 0.6, -3.0), 1e-6));
+        assert_eq!(ground_point(position, None, None), position);
+    }
+}
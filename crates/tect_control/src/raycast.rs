@@ -0,0 +1,85 @@
+///地面射线检测：对 Ground 场景下的真实网格做射线求交，取代无限平面求交
+///在山坡、斜坡、台阶上点击时可以得到正确的高度和表面法线
+use bevy::ecs::system::SystemParam;
+use bevy::picking::mesh_picking::ray_cast::{MeshRayCast, MeshRayCastSettings, RayCastVisibility};
+use bevy::prelude::*;
+
+use crate::moving::Ground;
+
+/// 主视角相机标记：场景中有多个相机（如小地图）时，屏幕射线只从它发出
+#[derive(Component, Debug, Default)]
+pub struct MainCamera;
+
+/// 鼠标是否悬停在可交互的 UI 上（带 Interaction 的节点，如小地图），此时点击不应作用于场景
+pub fn pointer_over_ui(interactions: &Query<&Interaction>) -> bool {
+    interactions
+        .iter()
+        .any(|interaction| *interaction != Interaction::None)
+}
+
+/// 射线命中地面的结果
+#[derive(Debug, Clone, Copy)]
+pub struct GroundHit {
+    /// 世界空间命中点
+    pub point: Vec3,
+    /// 命中三角形的表面法线（已归一化）
+    pub normal: Vec3,
+    /// 被命中的网格实体（Ground 场景中的子实体）
+    pub entity: Entity,
+}
+
+/// 只检测 Ground 场景根节点下网格的射线检测参数
+#[derive(SystemParam)]
+pub struct GroundRaycast<'w, 's> {
+    ray_cast: MeshRayCast<'w, 's>,
+    grounds: Query<'w, 's, (), With<Ground>>,
+    parents: Query<'w, 's, &'static ChildOf>,
+}
+
+impl GroundRaycast<'_, '_> {
+    /// 沿射线查找最近的地面命中点
+    pub fn cast_ray(&mut self, ray: Ray3d) -> Option<GroundHit> {
+        let Self {
+            ray_cast,
+            grounds,
+            parents,
+        } = self;
+
+        let filter = |entity: Entity| {
+            grounds.contains(entity)
+                || parents
+                    .iter_ancestors(entity)
+                    .any(|ancestor| grounds.contains(ancestor))
+        };
+        let settings = MeshRayCastSettings::default()
+            .with_filter(&filter)
+            .with_visibility(RayCastVisibility::Visible);
+
+        ray_cast
+            .cast_ray(ray, &settings)
+            .first()
+            .map(|(entity, hit)| GroundHit {
+                point: hit.point,
+                normal: hit.normal.normalize_or(Vec3::Y),
+                entity: *entity,
+            })
+    }
+
+    /// 从屏幕坐标发出射线检测地面（用于鼠标点击）
+    pub fn cast_from_viewport(
+        &mut self,
+        camera: &Camera,
+        camera_transform: &GlobalTransform,
+        viewport_position: Vec2,
+    ) -> Option<GroundHit> {
+        let ray = camera
+            .viewport_to_world(camera_transform, viewport_position)
+            .ok()?;
+        self.cast_ray(ray)
+    }
+
+    /// 从高处竖直向下检测 (x, z) 处的地面（用于小地图等只有平面坐标的场合）
+    pub fn cast_down(&mut self, x: f32, z: f32, height: f32) -> Option<GroundHit> {
+        self.cast_ray(Ray3d::new(Vec3::new(x, height, z), Dir3::NEG_Y))
+    }
+}
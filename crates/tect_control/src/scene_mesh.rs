@@ -0,0 +1,95 @@
+///场景网格读取：收集某个场景根节点下所有网格的世界空间三角形
+///导航网格烘焙与物理静态碰撞体都需要从 Ground 场景读取几何体，统一放在这里
+use bevy::ecs::system::SystemParam;
+use bevy::mesh::{Indices, PrimitiveTopology, VertexAttributeValues};
+use bevy::prelude::*;
+
+/// 读取场景层级中网格几何体的系统参数
+#[derive(SystemParam)]
+pub struct SceneMeshes<'w, 's> {
+    nodes: Query<
+        'w,
+        's,
+        (
+            &'static Transform,
+            Option<&'static Mesh3d>,
+            Option<&'static Children>,
+        ),
+    >,
+    global_transforms: Query<'w, 's, &'static GlobalTransform>,
+    meshes: Res<'w, Assets<Mesh>>,
+}
+
+impl SceneMeshes<'_, '_> {
+    /// 按网格实体分组返回 `root` 下所有三角形（世界空间）
+    /// 场景刚实例化完成时子实体的 GlobalTransform 还未传播，所以从根节点开始沿层级手动累乘 Transform
+    pub fn triangles_by_mesh(&self, root: Entity) -> Vec<(Entity, Vec<[Vec3; 3]>)> {
+        let root_transform = self
+            .global_transforms
+            .get(root)
+            .copied()
+            .unwrap_or_default();
+
+        let mut result = Vec::new();
+        let mut stack: Vec<(Entity, GlobalTransform)> = Vec::new();
+        if let Ok((_, _, Some(children))) = self.nodes.get(root) {
+            stack.extend(children.iter().map(|child| (child, root_transform)));
+        }
+
+        while let Some((entity, parent_transform)) = stack.pop() {
+            let Ok((transform, mesh, children)) = self.nodes.get(entity) else {
+                continue;
+            };
+            let global = parent_transform.mul_transform(*transform);
+            if let Some(mesh) = mesh.and_then(|m| self.meshes.get(&m.0)) {
+                let mut triangles = Vec::new();
+                collect_triangles(mesh, &global, &mut triangles);
+                if !triangles.is_empty() {
+                    result.push((entity, triangles));
+                }
+            }
+            if let Some(children) = children {
+                stack.extend(children.iter().map(|child| (child, global)));
+            }
+        }
+        result
+    }
+
+    /// 返回 `root` 下所有三角形（世界空间）
+    pub fn triangles(&self, root: Entity) -> Vec<[Vec3; 3]> {
+        self.triangles_by_mesh(root)
+            .into_iter()
+            .flat_map(|(_, triangles)| triangles)
+            .collect()
+    }
+}
+
+/// 读取网格的三角形并变换到世界空间
+fn collect_triangles(mesh: &Mesh, transform: &GlobalTransform, out: &mut Vec<[Vec3; 3]>) {
+    if mesh.primitive_topology() != PrimitiveTopology::TriangleList {
+        return;
+    }
+    let Some(VertexAttributeValues::Float32x3(positions)) =
+        mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+    else {
+        return;
+    };
+    let world = |i: usize| transform.transform_point(Vec3::from(positions[i]));
+
+    match mesh.indices() {
+        Some(indices) => {
+            let indices: Vec<usize> = match indices {
+                Indices::U16(v) => v.iter().map(|&i| i as usize).collect(),
+                Indices::U32(v) => v.iter().map(|&i| i as usize).collect(),
+            };
+            out.extend(
+                indices
+                    .chunks_exact(3)
+                    .map(|tri| [world(tri[0]), world(tri[1]), world(tri[2])]),
+            );
+        }
+        None => out.extend(
+            (0..positions.len() / 3).map(|t| [world(t * 3), world(t * 3 + 1), world(t * 3 + 2)]),
+        ),
+    }
+}
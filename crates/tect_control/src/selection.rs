@@ -0,0 +1,324 @@
+///单位选择：选择键（默认左键）点选、拖框多选（按住 Shift 追加），被选中的单位带 Selected 标记并在脚下绘制选择圈
+///右键移动命令只作用于 Selected 单位，多个单位会按方阵分散到目标点周围
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use tect_state::app_state::*;
+use tect_state::input::{ActionState, InputAction};
+
+use crate::moving::PlayerMove;
+use crate::raycast::{pointer_over_ui, MainCamera};
+use crate::terrain::TerrainFollow;
+
+pub struct SelectionPlugin;
+
+impl Plugin for SelectionPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                select_units_system.run_if(in_state(InGameState::Playing)),
+                draw_selection_rings,
+            )
+                .run_if(in_state(AppState::InGame))
+                .chain(),
+        );
+    }
+}
+
+const SELECTION_COLOR: Color = Color::srgb(0.0, 0.8, 1.0);
+// 拖动超过该像素距离才视为框选
+const BOX_SELECT_THRESHOLD: f32 = 5.0;
+// 点选时鼠标与单位屏幕位置的最大距离（像素）
+const CLICK_SELECT_RADIUS: f32 = 40.0;
+// 选择圈半径
+const SELECTION_RING_RADIUS: f32 = 0.6;
+/// 编队中相邻单位的间距
+pub const FORMATION_SPACING: f32 = 1.2;
+
+/// 被选中的单位
+#[derive(Component, Debug, Default)]
+pub struct Selected;
+
+/// 框选时显示的矩形 UI
+#[derive(Component)]
+struct SelectionBox;
+
+// ──────────────────────────────────────────────────────────────
+// 1. 点选 / 框选
+// ──────────────────────────────────────────────────────────────
+#[allow(clippy::too_many_arguments)]
+fn select_units_system(
+    mut commands: Commands,
+    actions: Res<ActionState>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    window: Single<&Window, With<PrimaryWindow>>,
+    camera_query: Single<(&Camera, &GlobalTransform), With<MainCamera>>,
+    interactions: Query<&Interaction>,
+    units: Query<(Entity, &GlobalTransform, Has<Selected>), With<PlayerMove>>,
+    mut selection_box: Query<(Entity, &mut Node), With<SelectionBox>>,
+    mut drag_start: Local<Option<Vec2>>,
+) {
+    let (camera, camera_transform) = *camera_query;
+    let cursor = window.cursor_position();
+
+    if actions.just_pressed(InputAction::Select) {
+        // 在小地图等 UI 上按下时不开始选择
+        *drag_start = cursor.filter(|_| !pointer_over_ui(&interactions));
+        return;
+    }
+    let Some(start) = *drag_start else {
+        return;
+    };
+    let current = cursor.unwrap_or(start);
+    let is_box = start.distance(current) > BOX_SELECT_THRESHOLD;
+
+    // 按住拖动：更新框选矩形
+    if actions.pressed(InputAction::Select) {
+        if is_box {
+            let (min, max) = (start.min(current), start.max(current));
+            match selection_box.single_mut() {
+                Ok((_, mut node)) => set_box_rect(&mut node, min, max),
+                Err(_) => {
+                    let mut node = Node {
+                        position_type: PositionType::Absolute,
+                        border: UiRect::all(Val::Px(1.0)),
+                        ..default()
+                    };
+                    set_box_rect(&mut node, min, max);
+                    commands.spawn((
+                        node,
+                        BackgroundColor(SELECTION_COLOR.with_alpha(0.15)),
+                        BorderColor::all(SELECTION_COLOR.with_alpha(0.8)),
+                        SelectionBox,
+                        DespawnOnExit(AppState::InGame),
+                    ));
+                }
+            }
+        }
+        return;
+    }
+
+    if !actions.just_released(InputAction::Select) {
+        return;
+    }
+    *drag_start = None;
+    for (entity, _) in selection_box.iter() {
+        commands.entity(entity).despawn();
+    }
+
+    let additive = keyboard.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+    if !additive {
+        for (entity, _, selected) in units.iter() {
+            if selected {
+                commands.entity(entity).remove::<Selected>();
+            }
+        }
+    }
+
+    let screen_position = |transform: &GlobalTransform| {
+        camera
+            .world_to_viewport(camera_transform, transform.translation())
+            .ok()
+    };
+
+    if is_box {
+        let rect = Rect::from_corners(start, current);
+        for (entity, transform, _) in units.iter() {
+            if screen_position(transform).is_some_and(|p| rect.contains(p)) {
+                commands.entity(entity).insert(Selected);
+            }
+        }
+    } else {
+        // 点选离鼠标最近的单位；Shift 点选已选中的单位则取消选择
+        let nearest = units
+            .iter()
+            .filter_map(|(entity, transform, selected)| {
+                let distance = screen_position(transform)?.distance(current);
+                (distance <= CLICK_SELECT_RADIUS).then_some((entity, selected, distance))
+            })
+            .min_by(|a, b| a.2.total_cmp(&b.2));
+        if let Some((entity, selected, _)) = nearest {
+            if additive && selected {
+                commands.entity(entity).remove::<Selected>();
+            } else {
+                commands.entity(entity).insert(Selected);
+            }
+        }
+    }
+}
+
+fn set_box_rect(node: &mut Node, min: Vec2, max: Vec2) {
+    node.left = Val::Px(min.x);
+    node.top = Val::Px(min.y);
+    node.width = Val::Px(max.x - min.x);
+    node.height = Val::Px(max.y - min.y);
+}
+
+// ──────────────────────────────────────────────────────────────
+// 2. 选择圈
+// ──────────────────────────────────────────────────────────────
+fn draw_selection_rings(
+    mut gizmos: Gizmos,
+    selected: Query<(&GlobalTransform, Option<&TerrainFollow>), With<Selected>>,
+) {
+    for (transform, follow) in selected.iter() {
+        let height_offset = follow.map_or(0.0, |f| f.height_offset);
+        let feet = transform.translation() - Vec3::Y * (height_offset - 0.05);
+        gizmos.circle(
+            Isometry3d::new(feet, Quat::from_rotation_x(std::f32::consts::FRAC_PI_2)),
+            SELECTION_RING_RADIUS,
+            SELECTION_COLOR,
+        );
+    }
+}
+
+// ──────────────────────────────────────────────────────────────
+// 3. 编队
+// ──────────────────────────────────────────────────────────────
+
+/// 以目标点为中心的方阵偏移，`facing` 为队伍前进方向（只取 XZ 分量）
+pub fn formation_offsets(count: usize, spacing: f32, facing: Vec3) -> Vec<Vec3> {
+    if count == 0 {
+        return Vec::new();
+    }
+    let forward = facing.with_y(0.0).try_normalize().unwrap_or(Vec3::NEG_Z);
+    let right = forward.cross(Vec3::Y);
+
+    let columns = (count as f32).sqrt().ceil() as usize;
+    let rows = count.div_ceil(columns);
+    (0..count)
+        .map(|i| {
+            let (row, column) = (i / columns, i % columns);
+            let in_row = columns.min(count - row * columns);
+            let x = (column as f32 - (in_row - 1) as f32 * 0.5) * spacing;
+            let z = ((rows - 1) as f32 * 0.5 - row as f32) * spacing;
+            right * x + forward * z
+        })
+        .collect()
+}
+
+/// 把一组单位分配到目标点周围的编队位置，每个槽位贪心地取最近的未分配单位
+pub fn assign_formation(
+    units: &[(Entity, Vec3)],
+    target: Vec3,
+    spacing: f32,
+) -> Vec<(Entity, Vec3)> {
+    if units.is_empty() {
+        return Vec::new();
+    }
+    let centroid = units.iter().map(|(_, p)| *p).sum::<Vec3>() / units.len() as f32;
+    let slots = formation_offsets(units.len(), spacing, target - centroid);
+
+    let mut remaining = units.to_vec();
+    slots
+        .into_iter()
+        .map(|offset| {
+            let slot = target + offset;
+            let (index, _) = remaining
+                .iter()
+                .enumerate()
+                .min_by(|(_, (_, a)), (_, (_, b))| {
+                    a.distance_squared(slot)
+                        .total_cmp(&b.distance_squared(slot))
+                })
+                .expect("编队槽位数与单位数相同");
+            let (entity, _) = remaining.swap_remove(index);
+            (entity, slot)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn units(positions: &[Vec3]) -> Vec<(Entity, Vec3)> {
+        positions
+            .iter()
+            .enumerate()
+            .map(|(i, p)| (Entity::from_raw_u32(i as u32 + 1).unwrap(), *p))
+            .collect()
+    }
+
+    #[test]
+    fn offsets_have_one_slot_per_unit_centered_on_target() {
+        assert!(formation_offsets(0, 1.5, Vec3::X).is_empty());
+        assert_eq!(formation_offsets(1, 1.5, Vec3::X), [Vec3::ZERO]);
+        for count in [2, 3, 5, 9, 10] {
+            let offsets = formation_offsets(count, 1.5, Vec3::X);
+            assert_eq!(offsets.len(), count);
+            assert!(offsets.iter().all(|offset| offset.y == 0.0));
+            // 整行排满时方阵以目标点为中心
+            if count == 9 {
+                let center = offsets.iter().sum::<Vec3>() / count as f32;
+                assert!(center.length() < 1e-5, "{center}");
+            }
+        }
+    }
+
+    #[test]
+    fn neighbouring_slots_are_spacing_apart() {
+        let spacing = 2.0;
+        let offsets = formation_offsets(7, spacing, Vec3::NEG_Z);
+        for (i, a) in offsets.iter().enumerate() {
+            let nearest = offsets
+                .iter()
+                .enumerate()
+                .filter(|(j, _)| *j != i)
+                .map(|(_, b)| a.distance(*b))
+                .fold(f32::INFINITY, f32::min);
+            assert!((nearest - spacing).abs() < 1e-4, "{i}: {nearest}");
+        }
+    }
+
+    #[test]
+    fn rows_are_laid_out_across_the_facing_direction() {
+        // 4 个单位排成 2x2，朝 +X 前进时第一排在前（+X），同一排沿 Z 展开
+        let offsets = formation_offsets(4, 1.0, Vec3::new(3.0, 5.0, 0.0));
+        assert!(offsets[0].x > 0.0 && offsets[1].x > 0.0);
+        assert!(offsets[2].x < 0.0 && offsets[3].x < 0.0);
+        assert_eq!(offsets[0].x, offsets[1].x);
+        assert!((offsets[0].z - offsets[1].z).abs() > 0.5);
+
+        // 没有水平方向时默认朝 -Z
+        let default = formation_offsets(4, 1.0, Vec3::Y);
+        assert!(default[0].z < 0.0 && default[2].z > 0.0);
+    }
+
+    #[test]
+    fn each_unit_gets_a_distinct_slot_around_target() {
+        let target = Vec3::new(10.0, 0.0, 10.0);
+        let group = units(&[
+            Vec3::new(0.0, 0.0, 0.0),
+            Vec3::new(1.0, 0.0, 0.0),
+            Vec3::new(0.0, 0.0, 1.0),
+            Vec3::new(1.0, 0.0, 1.0),
+            Vec3::new(2.0, 0.0, 0.5),
+        ]);
+        let assigned = assign_formation(&group, target, FORMATION_SPACING);
+        assert_eq!(assigned.len(), group.len());
+
+        let mut entities: Vec<Entity> = assigned.iter().map(|(entity, _)| *entity).collect();
+        entities.sort();
+        entities.dedup();
+        assert_eq!(entities.len(), group.len());
+
+        // 队伍朝向为从单位中心指向目标点
+        let centroid = group.iter().map(|(_, p)| *p).sum::<Vec3>() / group.len() as f32;
+        let expected = formation_offsets(group.len(), FORMATION_SPACING, target - centroid);
+        for (offset, (_, slot)) in expected.iter().zip(&assigned) {
+            assert!(slot.abs_diff_eq(target + *offset, 1e-5));
+        }
+    }
+
+    #[test]
+    fn assignment_keeps_units_on_their_side() {
+        // 两个单位左右并排向 -Z 前进，左边的单位应分到左边的槽位
+        let group = units(&[Vec3::new(-5.0, 0.0, 0.0), Vec3::new(5.0, 0.0, 0.0)]);
+        let assigned = assign_formation(&group, Vec3::new(0.0, 0.0, -20.0), 2.0);
+        for (entity, slot) in assigned {
+            let start = group.iter().find(|(e, _)| *e == entity).unwrap().1;
+            assert_eq!(slot.x.signum(), start.x.signum(), "{entity}: {slot}");
+        }
+    }
+}
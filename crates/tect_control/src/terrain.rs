@@ -0,0 +1,89 @@
+///地形跟随：每帧向下采样 PlayerMove 实体脚下的地面高度，让角色贴合起伏的地形
+///坡度超过上限时阻止继续前进，高度变化做平滑处理避免台阶处瞬间跳变
+use bevy::prelude::*;
+
+use crate::moving::PlayerMove;
+use crate::raycast::GroundRaycast;
+
+/// 地形跟随参数，PlayerMove 会自动带上默认值
+#[derive(Component, Debug, Clone)]
+#[require(GroundContact)]
+pub struct TerrainFollow {
+    /// 实体原点相对脚底的高度（模型原点不在脚底时使用）
+    pub height_offset: f32,
+    /// 可站立的最大坡度（弧度），超过时视为陡壁，停止移动
+    pub max_slope: f32,
+    /// 高度平滑系数，越大越快贴合地面（与帧率无关的指数衰减）
+    pub smoothing: f32,
+    /// 从脚底向上多高处开始向下采样，决定能自动登上的最大台阶高度
+    pub probe_height: f32,
+}
+
+impl Default for TerrainFollow {
+    fn default() -> Self {
+        Self {
+            height_offset: 0.0,
+            max_slope: 40f32.to_radians(),
+            smoothing: 12.0,
+            probe_height: 1.0,
+        }
+    }
+}
+
+/// 最近一次的地面采样结果
+#[derive(Component, Debug, Clone, Default)]
+pub struct GroundContact {
+    /// 脚下是否有地面
+    pub grounded: bool,
+    /// 脚下地面的法线
+    pub normal: Vec3,
+    /// 脚下地面的高度
+    pub ground_height: f32,
+    /// 最近一次站在可行走坡面上的位置，遇到陡壁时退回该位置
+    pub last_valid_position: Option<Vec3>,
+}
+
+/// 在移动之后运行：采样地面、处理坡度限制并平滑修正高度
+pub(crate) fn terrain_follow_system(
+    mut units: Query<(
+        &mut Transform,
+        &mut PlayerMove,
+        &TerrainFollow,
+        &mut GroundContact,
+    )>,
+    mut ground_raycast: GroundRaycast,
+    time: Res<Time>,
+) {
+    for (mut transform, mut player, follow, mut contact) in units.iter_mut() {
+        let feet = transform.translation - Vec3::Y * follow.height_offset;
+        let ray = Ray3d::new(feet + Vec3::Y * follow.probe_height, Dir3::NEG_Y);
+
+        let Some(hit) = ground_raycast.cast_ray(ray) else {
+            // 脚下没有地面（例如走出地图边缘），保持当前高度
+            contact.grounded = false;
+            continue;
+        };
+
+        let too_steep = hit.normal.angle_between(Vec3::Y) > follow.max_slope;
+        if too_steep && let Some(last_valid) = contact.last_valid_position {
+            // 陡壁：退回上一个合法位置并取消当前移动
+            transform.translation.x = last_valid.x;
+            transform.translation.z = last_valid.z;
+            player.target_position = None;
+            player.waypoints.clear();
+            continue;
+        }
+
+        contact.grounded = true;
+        contact.normal = hit.normal;
+        contact.ground_height = hit.point.y;
+
+        let target_y = hit.point.y + follow.height_offset;
+        let blend = 1.0 - (-follow.smoothing * time.delta_secs()).exp();
+        transform.translation.y = transform.translation.y.lerp(target_y, blend);
+
+        if !too_steep {
+            contact.last_valid_position = Some(transform.translation);
+        }
+    }
+}
@@ -1,11 +1,17 @@
-use bevy::{prelude::*};
+use bevy::prelude::*;
 
+use crate::input::InputActionPlugin;
+use crate::loading::LoadingPlugin;
+use crate::save::{PendingLoad, SaveGame, SaveSlots};
+use crate::settings::SettingsPlugin;
 
 //游戏主状态
 #[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash, States)]
 pub enum AppState {
     #[default]
     Menu,
+    /// 预加载地图、角色与特效资源，完成后进入 InGame
+    Loading,
     InGame,
 }
 
@@ -17,25 +23,34 @@ pub enum AppState {
 pub enum MenuOptions {
     #[default]
     NewGame,
-    ContinueGame, 
+    ContinueGame,
     OnlineGame,
     Setting,
-    About
+    About,
 }
 
+//游戏中子状态，暂停时冻结 Time<Virtual>，依赖时间的移动、动画与相机随之停止
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash, SubStates)]
+#[source(AppState = AppState::InGame)]
+#[states(scoped_entities)]
+pub enum InGameState {
+    #[default]
+    Playing,
+    Paused,
+}
 
 // --- 共享资源和状态定义 ---
 
-/// 鼠标右键的动作判定结果
-/// 作为全局资源，用于在相机控制 (模块一) 和角色移动 (模块二) 之间进行互斥。
-#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash, Resource)]
-pub enum RightMouseAction {
-    #[default]
-    None,
-    PressedJustNow,          // 刚按下，还没决定
-    WaitingForDecision,      // 按住中，还在犹豫
-    CameraDrag,              // 已经判定为拖动
-    CharacterMove,           // 短促点击 → 这一帧要移动角色
+/// 当前载入的地图，按地图保存的数据（相机书签、存档等）以它的 id 区分
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Resource)]
+pub struct CurrentMap {
+    pub id: String,
+}
+
+impl CurrentMap {
+    pub fn new(id: impl Into<String>) -> Self {
+        Self { id: id.into() }
+    }
 }
 
 //游戏共享资源与状态注册插件
@@ -43,8 +58,22 @@ pub struct GameStatePlugin;
 
 impl Plugin for GameStatePlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<RightMouseAction>()
-           .init_state::<AppState>()
-           .init_state::<MenuOptions>();
+        app.add_plugins((InputActionPlugin, LoadingPlugin, SettingsPlugin))
+            .init_resource::<PendingLoad>()
+            .init_resource::<SaveSlots>()
+            .add_message::<SaveGame>()
+            .init_state::<AppState>()
+            .init_state::<MenuOptions>()
+            .add_sub_state::<InGameState>()
+            .add_systems(OnEnter(InGameState::Paused), pause_virtual_time)
+            .add_systems(OnExit(InGameState::Paused), unpause_virtual_time);
     }
-}
\ No newline at end of file
+}
+
+fn pause_virtual_time(mut time: ResMut<Time<Virtual>>) {
+    time.pause();
+}
+
+fn unpause_virtual_time(mut time: ResMut<Time<Virtual>>) {
+    time.unpause();
+}
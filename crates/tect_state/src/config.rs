@@ -0,0 +1,43 @@
+///RON 配置文件读写：按键、相机等玩家可以直接编辑的配置都放在 config/ 目录下
+use bevy::prelude::*;
+use serde::{de::DeserializeOwned, Serialize};
+use std::path::Path;
+
+/// 读取 RON 配置；文件不存在时返回 None，解析失败时打印警告并返回 None
+pub fn load_ron<T: DeserializeOwned>(path: impl AsRef<Path>) -> Option<T> {
+    let path = path.as_ref();
+    let text = std::fs::read_to_string(path).ok()?;
+    match ron::from_str(&text) {
+        Ok(value) => Some(value),
+        Err(error) => {
+            warn!("配置文件 {} 解析失败，使用默认值: {error}", path.display());
+            None
+        }
+    }
+}
+
+/// 以易读的格式写出 RON 配置，目录不存在时自动创建
+pub fn save_ron<T: Serialize>(value: &T, path: impl AsRef<Path>) -> std::io::Result<()> {
+    let path = path.as_ref();
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let text = ron::ser::to_string_pretty(value, ron::ser::PrettyConfig::default())
+        .map_err(std::io::Error::other)?;
+    std::fs::write(path, text)
+}
+
+/// 读取配置，文件不存在时写出默认值，方便玩家直接编辑
+pub fn load_or_create<T: DeserializeOwned + Serialize + Default>(path: impl AsRef<Path>) -> T {
+    let path = path.as_ref();
+    if let Some(value) = load_ron(path) {
+        return value;
+    }
+    let value = T::default();
+    if !path.exists()
+        && let Err(error) = save_ron(&value, path)
+    {
+        warn!("无法写入默认配置 {}: {error}", path.display());
+    }
+    value
+}
@@ -0,0 +1,802 @@
+///输入动作层：游戏逻辑只读取 InputAction，具体按键由 InputMap 决定
+///绑定保存在 RON 文件中，同一动作可以同时绑定键盘、鼠标与手柄；设置界面把 RebindState 置为 Listening 即可运行时改键
+use bevy::input::mouse::MouseWheel;
+use bevy::input::InputSystems;
+use bevy::platform::collections::HashMap;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::config::{load_ron, save_ron};
+
+/// 玩家自定义绑定的保存位置
+pub const INPUT_MAP_PATH: &str = "config/input.ron";
+// 摇杆 / 扳机超过该值视为按下
+const ANALOG_PRESS_THRESHOLD: f32 = 0.5;
+// 摇杆死区，防止漂移
+const GAMEPAD_DEAD_ZONE: f32 = 0.15;
+/// 下达命令与选择时直接读取的修饰键：Shift 追加命令 / 追加选择，Ctrl 攻击移动，Alt 巡逻
+pub const COMMAND_MODIFIER_KEYS: [KeyCode; 6] = [
+    KeyCode::ShiftLeft,
+    KeyCode::ShiftRight,
+    KeyCode::ControlLeft,
+    KeyCode::ControlRight,
+    KeyCode::AltLeft,
+    KeyCode::AltRight,
+];
+
+pub struct InputActionPlugin;
+
+impl Plugin for InputActionPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ActionState>()
+            .init_resource::<RebindState>()
+            .add_systems(PreStartup, load_input_map)
+            .add_systems(PreUpdate, update_action_state.after(InputSystems));
+    }
+}
+
+// ──────────────────────────────────────────────────────────────
+// 1. 动作与绑定
+// ──────────────────────────────────────────────────────────────
+
+/// 游戏中的输入动作
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum InputAction {
+    /// 点选 / 框选单位
+    Select,
+    /// 在光标处下达移动命令
+    MoveCommand,
+    /// 按住拖动旋转相机；与 MoveCommand 绑定同一按键时，短按下达命令、按住拖动旋转
+    RotateCamera,
+    ZoomIn,
+    ZoomOut,
+    PanForward,
+    PanBack,
+    PanLeft,
+    PanRight,
+    RotateLeft,
+    RotateRight,
+    /// 按住时相机平移 / 旋转 / 缩放加速；不要绑定到 COMMAND_MODIFIER_KEYS，否则追加命令时相机也会加速
+    FastCamera,
+    /// 开关相机跟随选中单位
+    ToggleFollow,
+    /// 相机立即对准选中单位
+    CenterOnSelection,
+    /// 开关调试用的自由飞行相机
+    ToggleFreeCamera,
+    /// 打开 / 关闭暂停菜单；输入挂起（过场镜头）期间依然有效
+    Pause,
+    /// 跳转到相机书签；按住 StoreBookmark 时改为记录当前视角
+    Bookmark1,
+    Bookmark2,
+    Bookmark3,
+    Bookmark4,
+    Bookmark5,
+    Bookmark6,
+    Bookmark7,
+    Bookmark8,
+    /// 与书签键同时按下时记录书签
+    StoreBookmark,
+}
+
+impl InputAction {
+    pub const ALL: [InputAction; 25] = [
+        InputAction::Select,
+        InputAction::MoveCommand,
+        InputAction::RotateCamera,
+        InputAction::ZoomIn,
+        InputAction::ZoomOut,
+        InputAction::PanForward,
+        InputAction::PanBack,
+        InputAction::PanLeft,
+        InputAction::PanRight,
+        InputAction::RotateLeft,
+        InputAction::RotateRight,
+        InputAction::FastCamera,
+        InputAction::ToggleFollow,
+        InputAction::CenterOnSelection,
+        InputAction::ToggleFreeCamera,
+        InputAction::Pause,
+        InputAction::Bookmark1,
+        InputAction::Bookmark2,
+        InputAction::Bookmark3,
+        InputAction::Bookmark4,
+        InputAction::Bookmark5,
+        InputAction::Bookmark6,
+        InputAction::Bookmark7,
+        InputAction::Bookmark8,
+        InputAction::StoreBookmark,
+    ];
+
+    /// 相机书签槽位对应的动作
+    pub const BOOKMARKS: [InputAction; 8] = [
+        InputAction::Bookmark1,
+        InputAction::Bookmark2,
+        InputAction::Bookmark3,
+        InputAction::Bookmark4,
+        InputAction::Bookmark5,
+        InputAction::Bookmark6,
+        InputAction::Bookmark7,
+        InputAction::Bookmark8,
+    ];
+
+    /// 设置界面中显示的名称
+    pub fn label(&self) -> &'static str {
+        match self {
+            InputAction::Select => "选择",
+            InputAction::MoveCommand => "移动命令",
+            InputAction::RotateCamera => "旋转视角",
+            InputAction::ZoomIn => "拉近",
+            InputAction::ZoomOut => "拉远",
+            InputAction::PanForward => "视角前移",
+            InputAction::PanBack => "视角后移",
+            InputAction::PanLeft => "视角左移",
+            InputAction::PanRight => "视角右移",
+            InputAction::RotateLeft => "视角左转",
+            InputAction::RotateRight => "视角右转",
+            InputAction::FastCamera => "相机加速",
+            InputAction::ToggleFollow => "跟随单位",
+            InputAction::CenterOnSelection => "对准选中单位",
+            InputAction::ToggleFreeCamera => "自由相机",
+            InputAction::Pause => "暂停菜单",
+            InputAction::Bookmark1 => "相机书签 1",
+            InputAction::Bookmark2 => "相机书签 2",
+            InputAction::Bookmark3 => "相机书签 3",
+            InputAction::Bookmark4 => "相机书签 4",
+            InputAction::Bookmark5 => "相机书签 5",
+            InputAction::Bookmark6 => "相机书签 6",
+            InputAction::Bookmark7 => "相机书签 7",
+            InputAction::Bookmark8 => "相机书签 8",
+            InputAction::StoreBookmark => "记录书签",
+        }
+    }
+}
+
+/// 轴输入取哪一半
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AxisDirection {
+    Positive,
+    Negative,
+}
+
+impl AxisDirection {
+    fn apply(self, value: f32) -> f32 {
+        match self {
+            AxisDirection::Positive => value.max(0.0),
+            AxisDirection::Negative => (-value).max(0.0),
+        }
+    }
+}
+
+/// 一个物理输入
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum InputBinding {
+    Key(KeyCode),
+    Mouse(MouseButton),
+    GamepadButton(GamepadButton),
+    /// 摇杆的半轴，例如左摇杆向上
+    GamepadAxis(GamepadAxis, AxisDirection),
+    /// 滚轮向上 / 向下，产生的是一次性输入（见 ActionState::impulse）
+    MouseWheel(AxisDirection),
+}
+
+impl InputBinding {
+    pub fn is_gamepad(&self) -> bool {
+        matches!(
+            self,
+            InputBinding::GamepadButton(_) | InputBinding::GamepadAxis(..)
+        )
+    }
+}
+
+/// 动作到物理输入的映射
+#[derive(Resource, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InputMap {
+    bindings: BTreeMap<InputAction, Vec<InputBinding>>,
+}
+
+impl Default for InputMap {
+    fn default() -> Self {
+        use AxisDirection::*;
+        Self {
+            bindings: BTreeMap::from([
+                (
+                    InputAction::Select,
+                    vec![InputBinding::Mouse(MouseButton::Left)],
+                ),
+                (
+                    InputAction::MoveCommand,
+                    vec![
+                        InputBinding::Mouse(MouseButton::Right),
+                        InputBinding::GamepadButton(GamepadButton::South),
+                    ],
+                ),
+                (
+                    InputAction::RotateCamera,
+                    vec![InputBinding::Mouse(MouseButton::Right)],
+                ),
+                (
+                    InputAction::ZoomIn,
+                    vec![
+                        InputBinding::MouseWheel(Positive),
+                        InputBinding::Key(KeyCode::Equal),
+                        InputBinding::Key(KeyCode::NumpadAdd),
+                        InputBinding::GamepadButton(GamepadButton::RightTrigger),
+                    ],
+                ),
+                (
+                    InputAction::ZoomOut,
+                    vec![
+                        InputBinding::MouseWheel(Negative),
+                        InputBinding::Key(KeyCode::Minus),
+                        InputBinding::Key(KeyCode::NumpadSubtract),
+                        InputBinding::GamepadButton(GamepadButton::LeftTrigger),
+                    ],
+                ),
+                (
+                    InputAction::PanForward,
+                    vec![
+                        InputBinding::Key(KeyCode::KeyW),
+                        InputBinding::Key(KeyCode::ArrowUp),
+                        InputBinding::GamepadAxis(GamepadAxis::LeftStickY, Positive),
+                    ],
+                ),
+                (
+                    InputAction::PanBack,
+                    vec![
+                        InputBinding::Key(KeyCode::KeyS),
+                        InputBinding::Key(KeyCode::ArrowDown),
+                        InputBinding::GamepadAxis(GamepadAxis::LeftStickY, Negative),
+                    ],
+                ),
+                (
+                    InputAction::PanLeft,
+                    vec![
+                        InputBinding::Key(KeyCode::KeyA),
+                        InputBinding::Key(KeyCode::ArrowLeft),
+                        InputBinding::GamepadAxis(GamepadAxis::LeftStickX, Negative),
+                    ],
+                ),
+                (
+                    InputAction::PanRight,
+                    vec![
+                        InputBinding::Key(KeyCode::KeyD),
+                        InputBinding::Key(KeyCode::ArrowRight),
+                        InputBinding::GamepadAxis(GamepadAxis::LeftStickX, Positive),
+                    ],
+                ),
+                (
+                    InputAction::RotateLeft,
+                    vec![
+                        InputBinding::Key(KeyCode::KeyQ),
+                        InputBinding::GamepadAxis(GamepadAxis::RightStickX, Negative),
+                    ],
+                ),
+                (
+                    InputAction::RotateRight,
+                    vec![
+                        InputBinding::Key(KeyCode::KeyE),
+                        InputBinding::GamepadAxis(GamepadAxis::RightStickX, Positive),
+                    ],
+                ),
+                (
+                    InputAction::FastCamera,
+                    vec![
+                        InputBinding::Key(KeyCode::Tab),
+                        InputBinding::GamepadButton(GamepadButton::LeftThumb),
+                    ],
+                ),
+                (
+                    InputAction::ToggleFollow,
+                    vec![
+                        InputBinding::Key(KeyCode::KeyF),
+                        InputBinding::GamepadButton(GamepadButton::North),
+                    ],
+                ),
+                (
+                    InputAction::CenterOnSelection,
+                    vec![
+                        InputBinding::Key(KeyCode::Space),
+                        InputBinding::GamepadButton(GamepadButton::West),
+                    ],
+                ),
+                (
+                    InputAction::ToggleFreeCamera,
+                    vec![InputBinding::Key(KeyCode::F9)],
+                ),
+                (
+                    InputAction::Pause,
+                    vec![
+                        InputBinding::Key(KeyCode::Escape),
+                        InputBinding::GamepadButton(GamepadButton::Start),
+                    ],
+                ),
+                (InputAction::Bookmark1, vec![InputBinding::Key(KeyCode::F1)]),
+                (InputAction::Bookmark2, vec![InputBinding::Key(KeyCode::F2)]),
+                (InputAction::Bookmark3, vec![InputBinding::Key(KeyCode::F3)]),
+                (InputAction::Bookmark4, vec![InputBinding::Key(KeyCode::F4)]),
+                (InputAction::Bookmark5, vec![InputBinding::Key(KeyCode::F5)]),
+                (InputAction::Bookmark6, vec![InputBinding::Key(KeyCode::F6)]),
+                (InputAction::Bookmark7, vec![InputBinding::Key(KeyCode::F7)]),
+                (InputAction::Bookmark8, vec![InputBinding::Key(KeyCode::F8)]),
+                (
+                    InputAction::StoreBookmark,
+                    vec![
+                        InputBinding::Key(KeyCode::ControlLeft),
+                        InputBinding::Key(KeyCode::ControlRight),
+                    ],
+                ),
+            ]),
+        }
+    }
+}
+
+impl InputMap {
+    pub fn bindings(&self, action: InputAction) -> &[InputBinding] {
+        self.bindings.get(&action).map_or(&[], Vec::as_slice)
+    }
+
+    pub fn set_bindings(&mut self, action: InputAction, bindings: Vec<InputBinding>) {
+        self.bindings.insert(action, bindings);
+    }
+
+    /// 追加一个绑定（已存在时忽略）
+    pub fn bind(&mut self, action: InputAction, binding: InputBinding) {
+        let bindings = self.bindings.entry(action).or_default();
+        if !bindings.contains(&binding) {
+            bindings.push(binding);
+        }
+    }
+
+    /// 改键：替换该动作在同类设备（键鼠 / 手柄）上的绑定，另一类设备的绑定保留
+    pub fn rebind(&mut self, action: InputAction, binding: InputBinding) {
+        let bindings = self.bindings.entry(action).or_default();
+        bindings.retain(|b| b.is_gamepad() != binding.is_gamepad());
+        bindings.push(binding);
+    }
+
+    /// 绑定到该动作的鼠标按键，供手势识别的订阅者筛选
+    pub fn mouse_buttons(&self, action: InputAction) -> impl Iterator<Item = MouseButton> + '_ {
+        self.bindings(action)
+            .iter()
+            .filter_map(|binding| match binding {
+                InputBinding::Mouse(button) => Some(*button),
+                _ => None,
+            })
+    }
+
+    /// 两个动作是否共用了某个按键
+    pub fn shares_binding(&self, a: InputAction, b: InputAction) -> bool {
+        let other = self.bindings(b);
+        self.bindings(a)
+            .iter()
+            .any(|binding| other.contains(binding))
+    }
+
+    /// 从 RON 文件读取，文件中缺少的动作使用默认绑定
+    pub fn load(path: impl AsRef<Path>) -> Option<Self> {
+        let loaded: InputMap = load_ron(path)?;
+        let mut map = InputMap::default();
+        map.bindings.extend(loaded.bindings);
+        map.warn_modifier_conflicts();
+        Some(map)
+    }
+
+    /// 相机加速绑定到的命令修饰键
+    fn modifier_conflicts(&self) -> impl Iterator<Item = KeyCode> + '_ {
+        self.bindings(InputAction::FastCamera)
+            .iter()
+            .filter_map(|binding| match binding {
+                InputBinding::Key(key) if COMMAND_MODIFIER_KEYS.contains(key) => Some(*key),
+                _ => None,
+            })
+    }
+
+    /// 相机加速与命令修饰键共用按键时给出警告
+    fn warn_modifier_conflicts(&self) {
+        for key in self.modifier_conflicts() {
+            warn!(
+                "{} 绑定了命令修饰键 {key:?}，追加命令、框选或攻击移动时相机也会加速",
+                InputAction::FastCamera.label()
+            );
+        }
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        save_ron(self, path)
+    }
+}
+
+// ──────────────────────────────────────────────────────────────
+// 2. 每帧的动作状态
+// ──────────────────────────────────────────────────────────────
+
+/// 本帧各动作的输入量，游戏系统通过它读取输入
+#[derive(Resource, Debug, Default)]
+pub struct ActionState {
+    values: HashMap<InputAction, f32>,
+    previous: HashMap<InputAction, f32>,
+    impulses: HashMap<InputAction, f32>,
+    /// 暂停响应玩家输入（例如过场镜头播放期间），除 Pause 外的动作都视为未按下
+    suspended: bool,
+}
+
+impl ActionState {
+    /// 持续输入量：按键按下为 1，摇杆 / 扳机为 0~1
+    pub fn value(&self, action: InputAction) -> f32 {
+        self.values.get(&action).copied().unwrap_or(0.0)
+    }
+
+    /// 本帧的一次性输入量（滚轮滚动的格数）
+    pub fn impulse(&self, action: InputAction) -> f32 {
+        self.impulses.get(&action).copied().unwrap_or(0.0)
+    }
+
+    /// 正向动作减去反向动作的持续输入量
+    pub fn axis(&self, negative: InputAction, positive: InputAction) -> f32 {
+        self.value(positive) - self.value(negative)
+    }
+
+    pub fn pressed(&self, action: InputAction) -> bool {
+        self.value(action) > ANALOG_PRESS_THRESHOLD
+    }
+
+    pub fn just_pressed(&self, action: InputAction) -> bool {
+        self.pressed(action)
+            && self.previous.get(&action).copied().unwrap_or(0.0) <= ANALOG_PRESS_THRESHOLD
+    }
+
+    pub fn just_released(&self, action: InputAction) -> bool {
+        !self.pressed(action)
+            && self.previous.get(&action).copied().unwrap_or(0.0) > ANALOG_PRESS_THRESHOLD
+    }
+
+    pub fn is_suspended(&self) -> bool {
+        self.suspended
+    }
+
+    pub fn set_suspended(&mut self, suspended: bool) {
+        self.suspended = suspended;
+    }
+
+    fn clear(&mut self) {
+        self.previous = std::mem::take(&mut self.values);
+        self.impulses.clear();
+    }
+}
+
+/// 运行时改键状态：设置界面置为 Listening 后，下一个按下的键会绑定到该动作
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum RebindState {
+    #[default]
+    Idle,
+    Listening(InputAction),
+}
+
+fn load_input_map(mut commands: Commands) {
+    let map = InputMap::load(INPUT_MAP_PATH).unwrap_or_else(|| {
+        // 首次运行时写出默认配置，方便玩家直接编辑
+        let map = InputMap::default();
+        if !Path::new(INPUT_MAP_PATH).exists()
+            && let Err(error) = map.save(INPUT_MAP_PATH)
+        {
+            warn!("无法写入默认按键配置 {INPUT_MAP_PATH}: {error}");
+        }
+        map
+    });
+    commands.insert_resource(map);
+}
+
+fn update_action_state(
+    mut actions: ResMut<ActionState>,
+    mut input_map: ResMut<InputMap>,
+    mut rebind: ResMut<RebindState>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    mut wheel: MessageReader<MouseWheel>,
+    gamepads: Query<&Gamepad>,
+) {
+    let scroll: f32 = wheel.read().map(|event| event.y).sum();
+    actions.clear();
+
+    // 改键中：捕获下一个输入，期间游戏不响应任何动作
+    if let RebindState::Listening(action) = *rebind {
+        if keyboard.just_pressed(KeyCode::Escape) {
+            *rebind = RebindState::Idle;
+            return;
+        }
+        let captured = keyboard
+            .get_just_pressed()
+            .next()
+            .map(|key| InputBinding::Key(*key))
+            .or_else(|| {
+                mouse
+                    .get_just_pressed()
+                    .next()
+                    .map(|button| InputBinding::Mouse(*button))
+            })
+            .or_else(|| {
+                gamepads.iter().find_map(|gamepad| {
+                    gamepad
+                        .get_just_pressed()
+                        .next()
+                        .map(|button| InputBinding::GamepadButton(*button))
+                })
+            })
+            .or(match scroll {
+                y if y > 0.0 => Some(InputBinding::MouseWheel(AxisDirection::Positive)),
+                y if y < 0.0 => Some(InputBinding::MouseWheel(AxisDirection::Negative)),
+                _ => None,
+            });
+        if let Some(binding) = captured {
+            input_map.rebind(action, binding);
+            *rebind = RebindState::Idle;
+            if let Err(error) = input_map.save(INPUT_MAP_PATH) {
+                warn!("无法保存按键配置 {INPUT_MAP_PATH}: {error}");
+            }
+        }
+        return;
+    }
+    for (&action, bindings) in input_map.bindings.iter() {
+        // 挂起期间仍可打开暂停菜单，暂停时过场镜头随 Time<Virtual> 一起停下
+        if actions.suspended && action != InputAction::Pause {
+            continue;
+        }
+        let mut value: f32 = 0.0;
+        let mut impulse: f32 = 0.0;
+        for binding in bindings {
+            match *binding {
+                InputBinding::Key(key) if keyboard.pressed(key) => value = 1.0,
+                InputBinding::Mouse(button) if mouse.pressed(button) => value = 1.0,
+                InputBinding::GamepadButton(button) => {
+                    for gamepad in gamepads.iter() {
+                        let analog = gamepad.get(button).unwrap_or(0.0);
+                        value = value.max(if gamepad.pressed(button) { 1.0 } else { analog });
+                    }
+                }
+                InputBinding::GamepadAxis(axis, direction) => {
+                    for gamepad in gamepads.iter() {
+                        let raw = direction.apply(gamepad.get(axis).unwrap_or(0.0));
+                        if raw > GAMEPAD_DEAD_ZONE {
+                            value = value.max(raw);
+                        }
+                    }
+                }
+                InputBinding::MouseWheel(direction) => impulse += direction.apply(scroll),
+                _ => {}
+            }
+        }
+        if value > 0.0 {
+            actions.values.insert(action, value.min(1.0));
+        }
+        if impulse > 0.0 {
+            actions.impulses.insert(action, impulse);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::input::mouse::MouseScrollUnit;
+
+    /// 每个测试使用独立的临时配置文件，结束时删除
+    struct TempConfig(std::path::PathBuf);
+
+    impl TempConfig {
+        fn new(name: &str, contents: &str) -> Self {
+            let file = format!("tect_input_{}_{name}.ron", std::process::id());
+            let path = std::env::temp_dir().join(file);
+            std::fs::write(&path, contents).unwrap();
+            Self(path)
+        }
+    }
+
+    impl Drop for TempConfig {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_file(&self.0);
+        }
+    }
+
+    /// 只运行 update_action_state 的最小 App，按键由测试直接写入 ButtonInput
+    fn action_app() -> App {
+        let mut app = App::new();
+        app.init_resource::<ActionState>()
+            .init_resource::<RebindState>()
+            .init_resource::<InputMap>()
+            .init_resource::<ButtonInput<KeyCode>>()
+            .init_resource::<ButtonInput<MouseButton>>()
+            .add_message::<MouseWheel>()
+            .add_systems(Update, update_action_state);
+        app
+    }
+
+    fn press(app: &mut App, key: KeyCode) {
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(key);
+    }
+
+    fn release(app: &mut App, key: KeyCode) {
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .release(key);
+    }
+
+    fn actions(app: &App) -> &ActionState {
+        app.world().resource::<ActionState>()
+    }
+
+    #[test]
+    fn load_merges_partial_file_with_defaults() {
+        let config = TempConfig::new(
+            "partial",
+            "(bindings: { ToggleFollow: [Key(KeyG)], Pause: [Key(KeyP)] })",
+        );
+        let map = InputMap::load(&config.0).unwrap();
+        let defaults = InputMap::default();
+
+        assert_eq!(
+            map.bindings(InputAction::ToggleFollow),
+            [InputBinding::Key(KeyCode::KeyG)]
+        );
+        assert_eq!(
+            map.bindings(InputAction::Pause),
+            [InputBinding::Key(KeyCode::KeyP)]
+        );
+        // 文件中没有的动作保留默认绑定
+        for action in InputAction::ALL {
+            if !matches!(action, InputAction::ToggleFollow | InputAction::Pause) {
+                assert_eq!(
+                    map.bindings(action),
+                    defaults.bindings(action),
+                    "{action:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn load_rejects_missing_or_invalid_file() {
+        let missing = std::env::temp_dir().join("tect_input_does_not_exist.ron");
+        assert!(InputMap::load(missing).is_none());
+
+        let config = TempConfig::new("invalid", "(bindings: { Pause: [Key(NotAKey)] })");
+        assert!(InputMap::load(&config.0).is_none());
+    }
+
+    #[test]
+    fn rebind_replaces_only_same_device_class() {
+        let mut map = InputMap::default();
+        map.rebind(InputAction::ToggleFollow, InputBinding::Key(KeyCode::KeyG));
+        assert_eq!(
+            map.bindings(InputAction::ToggleFollow),
+            [
+                InputBinding::GamepadButton(GamepadButton::North),
+                InputBinding::Key(KeyCode::KeyG),
+            ]
+        );
+
+        // 键盘与鼠标同属一类，改成鼠标键会替换键盘绑定
+        map.rebind(
+            InputAction::ToggleFollow,
+            InputBinding::Mouse(MouseButton::Middle),
+        );
+        map.rebind(
+            InputAction::ToggleFollow,
+            InputBinding::GamepadButton(GamepadButton::East),
+        );
+        assert_eq!(
+            map.bindings(InputAction::ToggleFollow),
+            [
+                InputBinding::Mouse(MouseButton::Middle),
+                InputBinding::GamepadButton(GamepadButton::East),
+            ]
+        );
+    }
+
+    #[test]
+    fn fast_camera_on_modifier_key_is_a_conflict() {
+        let mut map = InputMap::default();
+        assert_eq!(map.modifier_conflicts().count(), 0);
+
+        map.bind(
+            InputAction::FastCamera,
+            InputBinding::Key(KeyCode::ShiftLeft),
+        );
+        map.bind(InputAction::FastCamera, InputBinding::Key(KeyCode::KeyZ));
+        assert_eq!(
+            map.modifier_conflicts().collect::<Vec<_>>(),
+            [KeyCode::ShiftLeft]
+        );
+    }
+
+    #[test]
+    fn key_press_sets_just_pressed_for_one_frame() {
+        let mut app = action_app();
+        press(&mut app, KeyCode::KeyW);
+        app.update();
+        assert!(actions(&app).just_pressed(InputAction::PanForward));
+        assert_eq!(
+            actions(&app).axis(InputAction::PanBack, InputAction::PanForward),
+            1.0
+        );
+
+        app.update();
+        assert!(actions(&app).pressed(InputAction::PanForward));
+        assert!(!actions(&app).just_pressed(InputAction::PanForward));
+
+        release(&mut app, KeyCode::KeyW);
+        app.update();
+        assert!(actions(&app).just_released(InputAction::PanForward));
+        app.update();
+        assert!(!actions(&app).just_released(InputAction::PanForward));
+    }
+
+    #[test]
+    fn mouse_wheel_is_an_impulse() {
+        let mut app = action_app();
+        app.world_mut().write_message(MouseWheel {
+            unit: MouseScrollUnit::Line,
+            x: 0.0,
+            y: 2.0,
+            window: Entity::PLACEHOLDER,
+        });
+        app.update();
+        assert_eq!(actions(&app).impulse(InputAction::ZoomIn), 2.0);
+        assert_eq!(actions(&app).impulse(InputAction::ZoomOut), 0.0);
+        assert!(!actions(&app).pressed(InputAction::ZoomIn));
+
+        app.update();
+        assert_eq!(actions(&app).impulse(InputAction::ZoomIn), 0.0);
+    }
+
+    #[test]
+    fn suspended_state_ignores_input_until_resumed() {
+        let mut app = action_app();
+        app.world_mut()
+            .resource_mut::<ActionState>()
+            .set_suspended(true);
+        press(&mut app, KeyCode::KeyW);
+        app.update();
+        assert!(!actions(&app).pressed(InputAction::PanForward));
+
+        // 恢复后仍按住的键视为刚按下
+        app.world_mut()
+            .resource_mut::<ActionState>()
+            .set_suspended(false);
+        app.update();
+        assert!(actions(&app).just_pressed(InputAction::PanForward));
+    }
+
+    #[test]
+    fn pause_still_works_while_suspended() {
+        let mut app = action_app();
+        app.world_mut()
+            .resource_mut::<ActionState>()
+            .set_suspended(true);
+        press(&mut app, KeyCode::Escape);
+        app.update();
+        assert!(actions(&app).just_pressed(InputAction::Pause));
+    }
+
+    #[test]
+    fn escape_cancels_rebinding_without_triggering_actions() {
+        let mut app = action_app();
+        *app.world_mut().resource_mut::<RebindState>() =
+            RebindState::Listening(InputAction::ToggleFollow);
+        press(&mut app, KeyCode::Escape);
+        app.update();
+
+        assert_eq!(*app.world().resource::<RebindState>(), RebindState::Idle);
+        assert!(!actions(&app).pressed(InputAction::Pause));
+        assert_eq!(
+            app.world()
+                .resource::<InputMap>()
+                .bindings(InputAction::ToggleFollow),
+            InputMap::default().bindings(InputAction::ToggleFollow)
+        );
+    }
+}
@@ -1 +1,6 @@
-pub mod app_state;
\ No newline at end of file
+pub mod app_state;
+pub mod config;
+pub mod input;
+pub mod loading;
+pub mod save;
+pub mod settings;
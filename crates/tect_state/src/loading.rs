@@ -0,0 +1,111 @@
+///资源预加载：进入 Loading 状态时各模块把进入游戏需要的资源登记到 LoadingAssets，全部加载完成（含依赖）后进入 InGame
+///任一资源加载失败时停留在 Loading，由加载界面显示错误并返回主菜单
+use bevy::asset::RecursiveDependencyLoadState;
+use bevy::prelude::*;
+
+use crate::app_state::AppState;
+
+pub struct LoadingPlugin;
+
+impl Plugin for LoadingPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<LoadingAssets>()
+            // 登记的句柄一直持有到回到主菜单，避免进入游戏时资源被释放后重新加载
+            .add_systems(OnEnter(AppState::Menu), clear_loading_assets)
+            .add_systems(Update, finish_loading.run_if(in_state(AppState::Loading)));
+    }
+}
+
+/// 加载失败的资源
+#[derive(Debug, Clone)]
+pub struct LoadingFailure {
+    pub label: String,
+    pub error: String,
+}
+
+/// 当前的加载进度
+#[derive(Debug, Clone, Default)]
+pub struct LoadingProgress {
+    pub loaded: usize,
+    pub total: usize,
+    pub failures: Vec<LoadingFailure>,
+}
+
+impl LoadingProgress {
+    /// 已加载的比例，没有登记任何资源时为 1
+    pub fn fraction(&self) -> f32 {
+        if self.total == 0 {
+            1.0
+        } else {
+            self.loaded as f32 / self.total as f32
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.loaded == self.total && self.failures.is_empty()
+    }
+}
+
+/// 进入游戏前需要加载完成的资源
+#[derive(Resource, Debug, Default)]
+pub struct LoadingAssets {
+    entries: Vec<(String, UntypedHandle)>,
+    /// 已经输出过失败日志
+    reported: bool,
+}
+
+impl LoadingAssets {
+    /// 登记资源，label 用于显示与日志（通常是资源路径）
+    pub fn add(&mut self, label: impl Into<String>, handle: impl Into<UntypedHandle>) {
+        self.entries.push((label.into(), handle.into()));
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.reported = false;
+    }
+
+    /// 按 AssetServer 的加载状态统计进度，资源连同其依赖全部加载完成才算完成
+    pub fn progress(&self, asset_server: &AssetServer) -> LoadingProgress {
+        let mut progress = LoadingProgress {
+            total: self.entries.len(),
+            ..default()
+        };
+        for (label, handle) in &self.entries {
+            match asset_server.get_recursive_dependency_load_state(handle.id()) {
+                Some(RecursiveDependencyLoadState::Loaded) => progress.loaded += 1,
+                Some(RecursiveDependencyLoadState::Failed(error)) => {
+                    progress.failures.push(LoadingFailure {
+                        label: label.clone(),
+                        error: error.to_string(),
+                    });
+                }
+                _ => {}
+            }
+        }
+        progress
+    }
+}
+
+fn clear_loading_assets(mut loading: ResMut<LoadingAssets>) {
+    loading.clear();
+}
+
+/// 全部加载完成后进入游戏；有资源失败时输出一次错误日志并停留在加载界面
+fn finish_loading(
+    asset_server: Res<AssetServer>,
+    mut loading: ResMut<LoadingAssets>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    let progress = loading.progress(&asset_server);
+    if progress.is_finished() {
+        next_state.set(AppState::InGame);
+        return;
+    }
+    if !progress.failures.is_empty() && !loading.reported {
+        loading.reported = true;
+        for failure in &progress.failures {
+            error!("资源 {} 加载失败: {}", failure.label, failure.error);
+        }
+    }
+}
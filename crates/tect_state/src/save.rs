@@ -0,0 +1,66 @@
+///存档请求：菜单与游戏内 UI 通过这里发起保存 / 读取，具体的序列化由 tect_world 的存档模块完成
+///各槽位的存档概要保存在 SaveSlots 中，存档列表据此显示而不必读取存档文件
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+
+/// 手动存档槽位数量
+pub const MANUAL_SAVE_SLOTS: u8 = 5;
+
+/// 存档槽位
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SaveSlot {
+    /// 手动存档，编号从 1 开始
+    Manual(u8),
+    /// 快速存档
+    Quick,
+    /// 定时自动存档
+    Auto,
+}
+
+impl SaveSlot {
+    /// 所有槽位
+    pub fn all() -> impl Iterator<Item = SaveSlot> {
+        (1..=MANUAL_SAVE_SLOTS)
+            .map(SaveSlot::Manual)
+            .chain([SaveSlot::Quick, SaveSlot::Auto])
+    }
+}
+
+impl fmt::Display for SaveSlot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SaveSlot::Manual(index) => write!(f, "manual_{index}"),
+            SaveSlot::Quick => write!(f, "quick"),
+            SaveSlot::Auto => write!(f, "auto"),
+        }
+    }
+}
+
+/// 槽位中已有存档的概要
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SaveSlotInfo {
+    /// 地图 id
+    pub map: String,
+    /// 保存时间（Unix 时间戳，秒）
+    pub saved_at: u64,
+}
+
+/// 各槽位的存档概要，启动时与每次保存成功后由存档模块更新；没有存档的槽位不在其中
+#[derive(Resource, Debug, Default)]
+pub struct SaveSlots(pub HashMap<SaveSlot, SaveSlotInfo>);
+
+/// 请求把当前游戏保存到指定槽位，只在 InGame 中处理
+#[derive(Message, Debug, Clone, Copy)]
+pub struct SaveGame(pub SaveSlot);
+
+/// 进入游戏时如何生成世界，在 Loading 开始时读取
+#[derive(Resource, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PendingLoad {
+    /// 开始新游戏
+    #[default]
+    NewGame,
+    /// 读取指定槽位，读取失败时开始新游戏
+    Slot(SaveSlot),
+}
@@ -0,0 +1,338 @@
+///游戏设置：图形、音频、操作与语言，保存在 config/settings.ron，修改后立即生效
+///窗口、阴影与音量在这里应用；相机灵敏度与边缘平移由 tect_camera 读取 GameSettings 应用
+use bevy::audio::Volume;
+use bevy::light::{DirectionalLightShadowMap, PointLightShadowMap};
+use bevy::prelude::*;
+use bevy::window::{MonitorSelection, PresentMode, PrimaryWindow, VideoModeSelection, WindowMode};
+use serde::{Deserialize, Serialize};
+
+use crate::config::{load_or_create, save_ron};
+
+/// 设置的保存位置
+pub const SETTINGS_PATH: &str = "config/settings.ron";
+
+/// 可选的窗口分辨率
+pub const RESOLUTIONS: [(u32, u32); 5] = [
+    (1280, 720),
+    (1600, 900),
+    (1920, 1080),
+    (2560, 1440),
+    (3840, 2160),
+];
+
+pub struct SettingsPlugin;
+
+impl Plugin for SettingsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GameSettings>()
+            .add_systems(PreStartup, load_game_settings)
+            .add_systems(
+                Update,
+                (
+                    apply_window_settings.run_if(resource_changed::<GameSettings>),
+                    apply_shadow_settings,
+                    apply_audio_settings,
+                ),
+            );
+    }
+}
+
+// ──────────────────────────────────────────────────────────────
+// 1. 设置项
+// ──────────────────────────────────────────────────────────────
+
+/// 全部设置
+#[derive(Resource, Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GameSettings {
+    pub graphics: GraphicsSettings,
+    pub audio: AudioSettings,
+    pub controls: ControlSettings,
+    pub language: Language,
+}
+
+impl GameSettings {
+    pub fn save(&self) {
+        if let Err(error) = save_ron(self, SETTINGS_PATH) {
+            warn!("无法保存设置 {SETTINGS_PATH}: {error}");
+        }
+    }
+}
+
+/// 图形设置
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GraphicsSettings {
+    /// 窗口分辨率（逻辑像素）
+    pub resolution: (u32, u32),
+    pub window_mode: WindowModeSetting,
+    pub vsync: bool,
+    pub shadows: ShadowQuality,
+}
+
+impl Default for GraphicsSettings {
+    fn default() -> Self {
+        Self {
+            resolution: (1280, 720),
+            window_mode: WindowModeSetting::Windowed,
+            vsync: true,
+            shadows: ShadowQuality::Medium,
+        }
+    }
+}
+
+/// 窗口模式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WindowModeSetting {
+    #[default]
+    Windowed,
+    /// 无边框全屏
+    Borderless,
+    /// 独占全屏
+    Fullscreen,
+}
+
+impl WindowModeSetting {
+    pub const ALL: [WindowModeSetting; 3] = [
+        WindowModeSetting::Windowed,
+        WindowModeSetting::Borderless,
+        WindowModeSetting::Fullscreen,
+    ];
+
+    pub fn label(self, language: Language) -> &'static str {
+        match self {
+            WindowModeSetting::Windowed => language.pick("Windowed", "窗口"),
+            WindowModeSetting::Borderless => language.pick("Borderless", "无边框全屏"),
+            WindowModeSetting::Fullscreen => language.pick("Fullscreen", "全屏"),
+        }
+    }
+
+    fn window_mode(self) -> WindowMode {
+        match self {
+            WindowModeSetting::Windowed => WindowMode::Windowed,
+            WindowModeSetting::Borderless => {
+                WindowMode::BorderlessFullscreen(MonitorSelection::Current)
+            }
+            WindowModeSetting::Fullscreen => {
+                WindowMode::Fullscreen(MonitorSelection::Current, VideoModeSelection::Current)
+            }
+        }
+    }
+}
+
+/// 阴影质量
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ShadowQuality {
+    Off,
+    Low,
+    #[default]
+    Medium,
+    High,
+}
+
+impl ShadowQuality {
+    pub const ALL: [ShadowQuality; 4] = [
+        ShadowQuality::Off,
+        ShadowQuality::Low,
+        ShadowQuality::Medium,
+        ShadowQuality::High,
+    ];
+
+    pub fn label(self, language: Language) -> &'static str {
+        match self {
+            ShadowQuality::Off => language.pick("Off", "关闭"),
+            ShadowQuality::Low => language.pick("Low", "低"),
+            ShadowQuality::Medium => language.pick("Medium", "中"),
+            ShadowQuality::High => language.pick("High", "高"),
+        }
+    }
+
+    /// 阴影贴图尺寸，关闭时为 None
+    pub fn map_size(self) -> Option<usize> {
+        match self {
+            ShadowQuality::Off => None,
+            ShadowQuality::Low => Some(1024),
+            ShadowQuality::Medium => Some(2048),
+            ShadowQuality::High => Some(4096),
+        }
+    }
+}
+
+/// 音量设置，取值 0~1
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AudioSettings {
+    pub master: f32,
+    pub music: f32,
+    pub effects: f32,
+}
+
+impl Default for AudioSettings {
+    fn default() -> Self {
+        Self {
+            master: 1.0,
+            music: 0.8,
+            effects: 1.0,
+        }
+    }
+}
+
+impl AudioSettings {
+    pub fn channel(&self, channel: SoundChannel) -> f32 {
+        match channel {
+            SoundChannel::Music => self.music,
+            SoundChannel::Effects => self.effects,
+        }
+    }
+
+    pub fn channel_mut(&mut self, channel: SoundChannel) -> &mut f32 {
+        match channel {
+            SoundChannel::Music => &mut self.music,
+            SoundChannel::Effects => &mut self.effects,
+        }
+    }
+}
+
+/// 音源所属的音量通道，带有该组件的音源音量 = 播放音量 × 主音量 × 通道音量
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SoundChannel {
+    Music,
+    Effects,
+}
+
+/// 操作设置
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ControlSettings {
+    /// 拖动旋转视角的灵敏度（弧度/像素），对应 GodViewCamera::sensitivity
+    pub camera_sensitivity: f32,
+    /// 鼠标推屏（窗口边缘平移），触发范围见 CameraSettings::edge_pan_threshold
+    pub edge_pan: bool,
+}
+
+impl Default for ControlSettings {
+    fn default() -> Self {
+        Self {
+            camera_sensitivity: 0.005,
+            edge_pan: true,
+        }
+    }
+}
+
+/// 界面语言
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Language {
+    #[default]
+    English,
+    Chinese,
+}
+
+impl Language {
+    pub const ALL: [Language; 2] = [Language::English, Language::Chinese];
+
+    /// 语言自身的名称
+    pub fn label(self) -> &'static str {
+        match self {
+            Language::English => "English",
+            Language::Chinese => "中文",
+        }
+    }
+
+    /// 按当前语言选择文本
+    pub fn pick(self, english: &'static str, chinese: &'static str) -> &'static str {
+        match self {
+            Language::English => english,
+            Language::Chinese => chinese,
+        }
+    }
+}
+
+// ──────────────────────────────────────────────────────────────
+// 2. 读取与应用
+// ──────────────────────────────────────────────────────────────
+
+fn load_game_settings(mut commands: Commands) {
+    commands.insert_resource(load_or_create::<GameSettings>(SETTINGS_PATH));
+}
+
+/// 只在分辨率、窗口模式或垂直同步变化时写入窗口，调整音量或语言时不会覆盖玩家手动调整的窗口大小
+fn apply_window_settings(
+    settings: Res<GameSettings>,
+    mut applied: Local<Option<GraphicsSettings>>,
+    mut window: Single<&mut Window, With<PrimaryWindow>>,
+) {
+    let graphics = &settings.graphics;
+    let unchanged = applied.as_ref().is_some_and(|previous| {
+        previous.resolution == graphics.resolution
+            && previous.window_mode == graphics.window_mode
+            && previous.vsync == graphics.vsync
+    });
+    if unchanged {
+        return;
+    }
+    *applied = Some(graphics.clone());
+
+    let (width, height) = graphics.resolution;
+    window.resolution.set(width as f32, height as f32);
+    window.mode = graphics.window_mode.window_mode();
+    window.present_mode = if graphics.vsync {
+        PresentMode::AutoVsync
+    } else {
+        PresentMode::AutoNoVsync
+    };
+}
+
+/// 设置变化时更新所有光源；之后生成的光源（例如地图 glTF 中的灯光）在出现时应用
+fn apply_shadow_settings(
+    settings: Res<GameSettings>,
+    mut directional_map: ResMut<DirectionalLightShadowMap>,
+    mut point_map: ResMut<PointLightShadowMap>,
+    mut directional_lights: Query<&mut DirectionalLight>,
+    mut point_lights: Query<&mut PointLight>,
+    mut spot_lights: Query<&mut SpotLight>,
+) {
+    let changed = settings.is_changed();
+    let map_size = settings.graphics.shadows.map_size();
+    let enabled = map_size.is_some();
+    if changed && let Some(size) = map_size {
+        directional_map.size = size;
+        point_map.size = size / 2;
+    }
+
+    for mut light in &mut directional_lights {
+        if changed || light.is_added() {
+            light.shadows_enabled = enabled;
+        }
+    }
+    for mut light in &mut point_lights {
+        if changed || light.is_added() {
+            light.shadows_enabled = enabled;
+        }
+    }
+    for mut light in &mut spot_lights {
+        if changed || light.is_added() {
+            light.shadows_enabled = enabled;
+        }
+    }
+}
+
+/// 音源音量 = 播放时设定的音量 × 主音量 × 通道音量（没有 SoundChannel 的音源只乘主音量）
+/// 设置变化时更新所有正在播放的音源；GlobalVolume 保持默认值，避免新音源重复乘上主音量
+fn apply_audio_settings(
+    settings: Res<GameSettings>,
+    mut sinks: Query<(
+        &mut AudioSink,
+        Option<&PlaybackSettings>,
+        Option<&SoundChannel>,
+    )>,
+) {
+    let audio = &settings.audio;
+    for (mut sink, playback, channel) in &mut sinks {
+        if !settings.is_changed() && !sink.is_added() {
+            continue;
+        }
+        let base = playback.map_or(Volume::Linear(1.0), |playback| playback.volume);
+        let channel_volume = channel.map_or(1.0, |channel| audio.channel(*channel));
+        sink.set_volume(base * Volume::Linear(audio.master * channel_volume));
+    }
+}
@@ -0,0 +1,675 @@
+///物理引擎集成：基于 rapier3d 提供刚体、Ground 场景静态碰撞体、PlayerMove 的运动学角色控制器与碰撞事件
+///外部使用时添加 PhysicsPlugin；需要物理的实体加上 RigidBody + Collider，
+///PlayerMove 实体会自动获得 CharacterController，玩法代码通过 MessageReader<CollisionEvent> 订阅碰撞
+use bevy::prelude::*;
+use bevy::scene::SceneInstanceReady;
+use bevy::transform::TransformSystems;
+use rapier3d::control::{CharacterAutostep, CharacterLength, KinematicCharacterController};
+use rapier3d::na::{Quaternion, UnitQuaternion};
+use rapier3d::prelude as rapier;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tect_control::moving::{Ground, PlayerMove};
+use tect_control::scene_mesh::SceneMeshes;
+use tect_state::app_state::*;
+
+pub struct PhysicsPlugin;
+
+impl Plugin for PhysicsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PhysicsSettings>()
+            .init_resource::<PhysicsWorld>()
+            .add_message::<CollisionEvent>()
+            .add_message::<CharacterCollisionEvent>()
+            .add_observer(attach_character_controller)
+            .add_observer(build_ground_colliders)
+            // 离开游戏时实体随 DespawnOnExit 销毁，下面的系统链已停止运行，直接清空物理世界
+            .add_systems(OnExit(AppState::InGame), reset_physics_world)
+            .add_systems(
+                PostUpdate,
+                (
+                    remove_despawned_bodies,
+                    create_bodies,
+                    create_character_bodies,
+                    move_characters,
+                    step_simulation,
+                    write_back_dynamic_bodies,
+                    emit_collision_events,
+                )
+                    .chain()
+                    .before(TransformSystems::Propagate)
+                    .run_if(in_state(AppState::InGame)),
+            );
+    }
+}
+
+// ──────────────────────────────────────────────────────────────
+// 1. 组件与资源
+// ──────────────────────────────────────────────────────────────
+
+/// 物理全局参数
+#[derive(Resource, Debug, Clone)]
+pub struct PhysicsSettings {
+    pub gravity: Vec3,
+    /// 单帧最大步长，防止卡顿后一次步进过大导致穿透
+    pub max_timestep: f32,
+}
+
+impl Default for PhysicsSettings {
+    fn default() -> Self {
+        Self {
+            gravity: Vec3::new(0.0, -9.81, 0.0),
+            max_timestep: 1.0 / 20.0,
+        }
+    }
+}
+
+/// 刚体类型（实体需为根实体，Transform 即世界坐标）
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+#[require(Velocity)]
+pub enum RigidBody {
+    /// 受力与碰撞影响，由物理世界驱动 Transform
+    Dynamic,
+    /// 固定不动
+    Fixed,
+    /// 由 Transform 驱动，推动动态刚体但自身不受力
+    Kinematic,
+}
+
+/// 碰撞体形状
+#[derive(Component, Debug, Clone)]
+pub enum Collider {
+    Ball {
+        radius: f32,
+    },
+    Cuboid {
+        half_extents: Vec3,
+    },
+    /// 沿 Y 轴的胶囊体，`half_height` 为中间圆柱段的一半高度
+    Capsule {
+        half_height: f32,
+        radius: f32,
+    },
+    TriMesh {
+        vertices: Vec<Vec3>,
+        indices: Vec<[u32; 3]>,
+    },
+}
+
+/// 刚体速度，动态刚体每步从物理世界同步回来
+#[derive(Component, Debug, Clone, Copy, Default)]
+pub struct Velocity {
+    pub linear: Vec3,
+    pub angular: Vec3,
+}
+
+/// 运动学角色控制器：移动系统写入的 Transform 作为期望位置，
+/// 物理步进前用胶囊体做扫掠，沿障碍物滑动并修正为实际可到达的位置
+#[derive(Component, Debug, Clone)]
+pub struct CharacterController {
+    pub radius: f32,
+    /// 胶囊体中间圆柱段的一半高度
+    pub half_height: f32,
+    /// 胶囊体中心相对实体原点的偏移
+    pub offset: Vec3,
+    /// 可自动跨上的台阶高度
+    pub max_step_height: f32,
+    /// 可爬上的最大坡度（弧度）
+    pub max_slope: f32,
+    /// 上一次移动是否贴地
+    pub grounded: bool,
+}
+
+impl Default for CharacterController {
+    fn default() -> Self {
+        Self {
+            radius: 0.3,
+            half_height: 0.5,
+            offset: Vec3::ZERO,
+            max_step_height: 0.3,
+            max_slope: 45f32.to_radians(),
+            grounded: false,
+        }
+    }
+}
+
+/// 两个碰撞体开始或结束接触，参与的实体至少一方需要开启碰撞事件（默认全部开启）
+#[derive(Message, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CollisionEvent {
+    Started(Entity, Entity),
+    Stopped(Entity, Entity),
+}
+
+/// 角色控制器移动时撞到了其他碰撞体（每帧每个接触对至多一条）
+#[derive(Message, Debug, Clone, Copy)]
+pub struct CharacterCollisionEvent {
+    pub character: Entity,
+    pub other: Entity,
+    /// 接触点处指向角色外侧的法线
+    pub normal: Vec3,
+}
+
+/// 实体在物理世界中的句柄
+#[derive(Component, Debug, Clone, Copy)]
+struct PhysicsHandle {
+    body: Option<rapier::RigidBodyHandle>,
+    collider: rapier::ColliderHandle,
+}
+
+/// rapier 物理世界的全部状态
+#[derive(Resource, Default)]
+pub struct PhysicsWorld {
+    pub bodies: rapier::RigidBodySet,
+    pub colliders: rapier::ColliderSet,
+    pub query_pipeline: rapier::QueryPipeline,
+    integration_parameters: rapier::IntegrationParameters,
+    pipeline: rapier::PhysicsPipeline,
+    islands: rapier::IslandManager,
+    broad_phase: rapier::DefaultBroadPhase,
+    narrow_phase: rapier::NarrowPhase,
+    impulse_joints: rapier::ImpulseJointSet,
+    multibody_joints: rapier::MultibodyJointSet,
+    ccd_solver: rapier::CCDSolver,
+    events: CollisionCollector,
+    /// 用于实体销毁后回收物理对象
+    handles: HashMap<Entity, PhysicsHandle>,
+}
+
+impl PhysicsWorld {
+    /// 碰撞体对应的实体
+    pub fn collider_entity(&self, handle: rapier::ColliderHandle) -> Option<Entity> {
+        self.colliders
+            .get(handle)
+            .and_then(|collider| Entity::try_from_bits(collider.user_data as u64))
+    }
+
+    fn insert(
+        &mut self,
+        entity: Entity,
+        body: Option<rapier::RigidBody>,
+        collider: rapier::ColliderBuilder,
+    ) -> PhysicsHandle {
+        let collider = collider
+            .user_data(entity.to_bits() as u128)
+            .active_events(rapier::ActiveEvents::COLLISION_EVENTS)
+            .active_collision_types(rapier::ActiveCollisionTypes::all());
+        let handle = match body {
+            Some(body) => {
+                let body = self.bodies.insert(body);
+                let collider = self
+                    .colliders
+                    .insert_with_parent(collider, body, &mut self.bodies);
+                PhysicsHandle {
+                    body: Some(body),
+                    collider,
+                }
+            }
+            None => PhysicsHandle {
+                body: None,
+                collider: self.colliders.insert(collider),
+            },
+        };
+        self.handles.insert(entity, handle);
+        handle
+    }
+
+    fn remove(&mut self, entity: Entity) {
+        let Some(handle) = self.handles.remove(&entity) else {
+            return;
+        };
+        match handle.body {
+            Some(body) => {
+                self.bodies.remove(
+                    body,
+                    &mut self.islands,
+                    &mut self.colliders,
+                    &mut self.impulse_joints,
+                    &mut self.multibody_joints,
+                    true,
+                );
+            }
+            None => {
+                self.colliders
+                    .remove(handle.collider, &mut self.islands, &mut self.bodies, false);
+            }
+        }
+    }
+}
+
+/// 收集物理步进中产生的碰撞事件
+#[derive(Default)]
+struct CollisionCollector(Mutex<Vec<rapier::CollisionEvent>>);
+
+impl rapier::EventHandler for CollisionCollector {
+    fn handle_collision_event(
+        &self,
+        _bodies: &rapier::RigidBodySet,
+        _colliders: &rapier::ColliderSet,
+        event: rapier::CollisionEvent,
+        _contact_pair: Option<&rapier::ContactPair>,
+    ) {
+        if let Ok(mut events) = self.0.lock() {
+            events.push(event);
+        }
+    }
+
+    fn handle_contact_force_event(
+        &self,
+        _dt: rapier::Real,
+        _bodies: &rapier::RigidBodySet,
+        _colliders: &rapier::ColliderSet,
+        _contact_pair: &rapier::ContactPair,
+        _total_force_magnitude: rapier::Real,
+    ) {
+    }
+}
+
+// ──────────────────────────────────────────────────────────────
+// 2. glam 与 nalgebra 之间的转换
+// ──────────────────────────────────────────────────────────────
+fn to_vector(v: Vec3) -> rapier::Vector<rapier::Real> {
+    rapier::Vector::new(v.x, v.y, v.z)
+}
+
+fn to_isometry(translation: Vec3, rotation: Quat) -> rapier::Isometry<rapier::Real> {
+    rapier::Isometry::from_parts(
+        rapier::Translation::new(translation.x, translation.y, translation.z),
+        UnitQuaternion::from_quaternion(Quaternion::new(
+            rotation.w, rotation.x, rotation.y, rotation.z,
+        )),
+    )
+}
+
+fn from_vector(v: &rapier::Vector<rapier::Real>) -> Vec3 {
+    Vec3::new(v.x, v.y, v.z)
+}
+
+fn from_rotation(r: &rapier::Rotation<rapier::Real>) -> Quat {
+    Quat::from_xyzw(r.i, r.j, r.k, r.w)
+}
+
+impl Collider {
+    fn builder(&self) -> Option<rapier::ColliderBuilder> {
+        match self {
+            Collider::Ball { radius } => Some(rapier::ColliderBuilder::ball(*radius)),
+            Collider::Cuboid { half_extents } => Some(rapier::ColliderBuilder::cuboid(
+                half_extents.x,
+                half_extents.y,
+                half_extents.z,
+            )),
+            Collider::Capsule {
+                half_height,
+                radius,
+            } => Some(rapier::ColliderBuilder::capsule_y(*half_height, *radius)),
+            Collider::TriMesh { vertices, indices } => {
+                let vertices = vertices.iter().map(|v| rapier::Point::new(v.x, v.y, v.z));
+                rapier::ColliderBuilder::trimesh(vertices.collect(), indices.clone())
+                    .inspect_err(|err| warn!("三角网格碰撞体创建失败：{err}"))
+                    .ok()
+            }
+        }
+    }
+}
+
+// ──────────────────────────────────────────────────────────────
+// 3. 创建与回收物理对象
+// ──────────────────────────────────────────────────────────────
+
+/// PlayerMove 实体自动获得默认的角色控制器（已手动指定的保持不变）
+fn attach_character_controller(add: On<Add, PlayerMove>, mut commands: Commands) {
+    commands
+        .entity(add.entity)
+        .insert_if_new(CharacterController::default());
+}
+
+/// Ground 场景实例化完成后，为其中每个网格生成固定的三角网格碰撞体
+fn build_ground_colliders(
+    ready: On<SceneInstanceReady>,
+    mut commands: Commands,
+    grounds: Query<(), With<Ground>>,
+    scene_meshes: SceneMeshes,
+    mut world: ResMut<PhysicsWorld>,
+) {
+    if !grounds.contains(ready.entity) {
+        return;
+    }
+
+    for (entity, triangles) in scene_meshes.triangles_by_mesh(ready.entity) {
+        let vertices = triangles.iter().flatten().copied().collect();
+        let indices = (0..triangles.len() as u32)
+            .map(|i| [i * 3, i * 3 + 1, i * 3 + 2])
+            .collect();
+        let Some(collider) = (Collider::TriMesh { vertices, indices }).builder() else {
+            continue;
+        };
+        let handle = world.insert(entity, None, collider);
+        commands.entity(entity).insert(handle);
+    }
+}
+
+fn create_bodies(
+    mut commands: Commands,
+    mut world: ResMut<PhysicsWorld>,
+    new_bodies: Query<
+        (Entity, &RigidBody, &Collider, &Transform, &Velocity),
+        Without<PhysicsHandle>,
+    >,
+) {
+    for (entity, body_type, collider, transform, velocity) in new_bodies.iter() {
+        let Some(collider) = collider.builder() else {
+            continue;
+        };
+        let builder = match body_type {
+            RigidBody::Dynamic => rapier::RigidBodyBuilder::dynamic(),
+            RigidBody::Fixed => rapier::RigidBodyBuilder::fixed(),
+            RigidBody::Kinematic => rapier::RigidBodyBuilder::kinematic_position_based(),
+        };
+        let body = builder
+            .position(to_isometry(transform.translation, transform.rotation))
+            .linvel(to_vector(velocity.linear))
+            .angvel(to_vector(velocity.angular))
+            .build();
+        let handle = world.insert(entity, Some(body), collider);
+        commands.entity(entity).insert(handle);
+    }
+}
+
+fn create_character_bodies(
+    mut commands: Commands,
+    mut world: ResMut<PhysicsWorld>,
+    new_characters: Query<(Entity, &CharacterController, &Transform), Without<PhysicsHandle>>,
+) {
+    for (entity, controller, transform) in new_characters.iter() {
+        let body = rapier::RigidBodyBuilder::kinematic_position_based()
+            .position(to_isometry(transform.translation, transform.rotation))
+            .build();
+        let collider =
+            rapier::ColliderBuilder::capsule_y(controller.half_height, controller.radius)
+                .translation(to_vector(controller.offset));
+        let handle = world.insert(entity, Some(body), collider);
+        commands.entity(entity).insert(handle);
+    }
+}
+
+fn remove_despawned_bodies(
+    mut world: ResMut<PhysicsWorld>,
+    mut removed: RemovedComponents<PhysicsHandle>,
+) {
+    for entity in removed.read() {
+        world.remove(entity);
+    }
+}
+
+/// 清空物理世界中的全部刚体、碰撞体与待处理的碰撞事件
+fn reset_physics_world(mut world: ResMut<PhysicsWorld>) {
+    *world = PhysicsWorld::default();
+}
+
+// ──────────────────────────────────────────────────────────────
+// 4. 每帧步进
+// ──────────────────────────────────────────────────────────────
+
+/// 把移动系统给出的位移交给角色控制器扫掠，撞墙时沿墙滑动
+fn move_characters(
+    mut world: ResMut<PhysicsWorld>,
+    mut characters: Query<(
+        Entity,
+        &mut Transform,
+        &mut CharacterController,
+        &PhysicsHandle,
+    )>,
+    mut collisions: MessageWriter<CharacterCollisionEvent>,
+    settings: Res<PhysicsSettings>,
+    time: Res<Time>,
+) {
+    let dt = time.delta_secs().min(settings.max_timestep);
+    if dt <= 0.0 {
+        return;
+    }
+
+    let world = world.as_mut();
+    world.query_pipeline.update(&world.colliders);
+
+    for (entity, mut transform, mut controller, handle) in characters.iter_mut() {
+        let Some(body_handle) = handle.body else {
+            continue;
+        };
+        let Some(collider) = world.colliders.get(handle.collider) else {
+            continue;
+        };
+        let Some(body) = world.bodies.get(body_handle) else {
+            continue;
+        };
+
+        let current = from_vector(body.translation());
+        let desired = transform.translation - current;
+        if desired.length_squared() < f32::EPSILON {
+            continue;
+        }
+
+        let kcc = KinematicCharacterController {
+            autostep: Some(CharacterAutostep {
+                max_height: CharacterLength::Absolute(controller.max_step_height),
+                min_width: CharacterLength::Absolute(controller.radius),
+                include_dynamic_bodies: false,
+            }),
+            max_slope_climb_angle: controller.max_slope,
+            snap_to_ground: None,
+            ..default()
+        };
+
+        let mut hits = Vec::new();
+        let movement = kcc.move_shape(
+            dt,
+            &world.bodies,
+            &world.colliders,
+            &world.query_pipeline,
+            collider.shape(),
+            collider.position(),
+            to_vector(desired),
+            rapier::QueryFilter::default()
+                .exclude_rigid_body(body_handle)
+                .exclude_sensors(),
+            |collision| hits.push(collision),
+        );
+
+        controller.grounded = movement.grounded;
+        let resolved = current + from_vector(&movement.translation);
+        transform.translation = resolved;
+        if let Some(body) = world.bodies.get_mut(body_handle) {
+            body.set_next_kinematic_position(to_isometry(resolved, transform.rotation));
+        }
+
+        let mut reported = Vec::new();
+        for hit in hits {
+            if reported.contains(&hit.handle) {
+                continue;
+            }
+            reported.push(hit.handle);
+            if let Some(other) = world.collider_entity(hit.handle) {
+                collisions.write(CharacterCollisionEvent {
+                    character: entity,
+                    other,
+                    normal: from_vector(&hit.hit.normal1),
+                });
+            }
+        }
+    }
+}
+
+/// 同步运动学刚体并推进物理世界
+fn step_simulation(
+    mut world: ResMut<PhysicsWorld>,
+    kinematic: Query<(&Transform, &RigidBody, &PhysicsHandle)>,
+    settings: Res<PhysicsSettings>,
+    time: Res<Time>,
+) {
+    let dt = time.delta_secs().min(settings.max_timestep);
+    if dt <= 0.0 {
+        return;
+    }
+
+    let world = world.as_mut();
+    for (transform, body_type, handle) in kinematic.iter() {
+        if *body_type == RigidBody::Kinematic
+            && let Some(body) = handle.body.and_then(|h| world.bodies.get_mut(h))
+        {
+            body.set_next_kinematic_position(to_isometry(
+                transform.translation,
+                transform.rotation,
+            ));
+        }
+    }
+
+    world.integration_parameters.dt = dt;
+    world.pipeline.step(
+        &to_vector(settings.gravity),
+        &world.integration_parameters,
+        &mut world.islands,
+        &mut world.broad_phase,
+        &mut world.narrow_phase,
+        &mut world.bodies,
+        &mut world.colliders,
+        &mut world.impulse_joints,
+        &mut world.multibody_joints,
+        &mut world.ccd_solver,
+        Some(&mut world.query_pipeline),
+        &(),
+        &world.events,
+    );
+}
+
+/// 动态刚体的位姿与速度写回 Transform / Velocity
+fn write_back_dynamic_bodies(
+    world: Res<PhysicsWorld>,
+    mut bodies: Query<(&mut Transform, &mut Velocity, &RigidBody, &PhysicsHandle)>,
+) {
+    for (mut transform, mut velocity, body_type, handle) in bodies.iter_mut() {
+        if *body_type != RigidBody::Dynamic {
+            continue;
+        }
+        let Some(body) = handle.body.and_then(|h| world.bodies.get(h)) else {
+            continue;
+        };
+        transform.translation = from_vector(body.translation());
+        transform.rotation = from_rotation(body.rotation());
+        velocity.linear = from_vector(body.linvel());
+        velocity.angular = from_vector(body.angvel());
+    }
+}
+
+fn emit_collision_events(world: Res<PhysicsWorld>, mut writer: MessageWriter<CollisionEvent>) {
+    let Ok(mut events) = world.events.0.lock() else {
+        return;
+    };
+    for event in events.drain(..) {
+        let (a, b) = (event.collider1(), event.collider2());
+        let (Some(a), Some(b)) = (world.collider_entity(a), world.collider_entity(b)) else {
+            continue;
+        };
+        writer.write(if event.started() {
+            CollisionEvent::Started(a, b)
+        } else {
+            CollisionEvent::Stopped(a, b)
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::state::app::StatesPlugin;
+    use bevy::time::TimeUpdateStrategy;
+    use std::time::Duration;
+
+    /// 已进入 InGame、每帧固定前进 16ms 的最小 App
+    fn test_app() -> App {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, StatesPlugin, PhysicsPlugin))
+            .init_state::<AppState>()
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+                16,
+            )));
+        app.world_mut()
+            .resource_mut::<NextState<AppState>>()
+            .set(AppState::InGame);
+        app.update();
+        app
+    }
+
+    fn ball(app: &mut App, translation: Vec3) -> Entity {
+        app.world_mut()
+            .spawn((
+                RigidBody::Dynamic,
+                Collider::Ball { radius: 0.5 },
+                Transform::from_translation(translation),
+                DespawnOnExit(AppState::InGame),
+            ))
+            .id()
+    }
+
+    fn physics_counts(app: &App) -> (usize, usize) {
+        let world = app.world().resource::<PhysicsWorld>();
+        (world.bodies.len(), world.colliders.len())
+    }
+
+    #[test]
+    fn creates_body_and_collider_for_new_entities() {
+        let mut app = test_app();
+        let entity = ball(&mut app, Vec3::ZERO);
+        app.update();
+
+        assert!(app.world().entity(entity).contains::<PhysicsHandle>());
+        assert_eq!(physics_counts(&app), (1, 1));
+
+        let world = app.world().resource::<PhysicsWorld>();
+        let handle = world.handles[&entity];
+        assert_eq!(world.collider_entity(handle.collider), Some(entity));
+    }
+
+    #[test]
+    fn removes_body_when_entity_is_despawned() {
+        let mut app = test_app();
+        let entity = ball(&mut app, Vec3::ZERO);
+        app.update();
+        assert_eq!(physics_counts(&app), (1, 1));
+
+        app.world_mut().despawn(entity);
+        app.update();
+        assert_eq!(physics_counts(&app), (0, 0));
+        assert!(app.world().resource::<PhysicsWorld>().handles.is_empty());
+    }
+
+    #[test]
+    fn leaving_in_game_clears_physics_world() {
+        let mut app = test_app();
+        ball(&mut app, Vec3::ZERO);
+        app.update();
+        assert_eq!(physics_counts(&app), (1, 1));
+
+        app.world_mut()
+            .resource_mut::<NextState<AppState>>()
+            .set(AppState::Menu);
+        app.update();
+        assert_eq!(physics_counts(&app), (0, 0));
+        assert!(app.world().resource::<PhysicsWorld>().handles.is_empty());
+    }
+
+    #[test]
+    fn maps_collision_events_to_entities() {
+        let mut app = test_app();
+        let a = ball(&mut app, Vec3::ZERO);
+        let b = ball(&mut app, Vec3::new(0.5, 0.0, 0.0));
+        // 第一帧创建刚体，第二帧步进时两球相交
+        app.update();
+        app.update();
+
+        let messages = app.world().resource::<Messages<CollisionEvent>>();
+        let events: Vec<_> = messages.get_cursor().read(messages).copied().collect();
+        assert!(
+            events.contains(&CollisionEvent::Started(a, b))
+                || events.contains(&CollisionEvent::Started(b, a)),
+            "{events:?}"
+        );
+    }
+}
@@ -1 +1,7 @@
-pub mod main_ui;
\ No newline at end of file
+pub mod loading_screen;
+pub mod localized_text;
+pub mod main_ui;
+pub mod minimap;
+pub mod pause_menu;
+pub mod save_slots;
+pub mod settings_menu;
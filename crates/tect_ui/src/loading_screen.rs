@@ -0,0 +1,195 @@
+///加载界面：Loading 状态下显示 LoadingAssets 的加载进度条，资源全部就绪后由 tect_state 切换到 InGame
+///有资源加载失败时列出失败的资源与原因，玩家可返回主菜单
+use bevy::prelude::*;
+use tect_state::app_state::*;
+use tect_state::loading::LoadingAssets;
+
+use crate::localized_text::LocalizedText;
+
+pub struct LoadingScreenPlugin;
+
+impl Plugin for LoadingScreenPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(AppState::Loading), spawn_loading_screen)
+            .add_systems(
+                Update,
+                (update_loading_screen, loading_back_button)
+                    .run_if(in_state(AppState::Loading))
+                    .chain(),
+            );
+    }
+}
+
+const BG_COLOR: Color = Color::srgb(0.05, 0.05, 0.12);
+const TRACK_COLOR: Color = Color::srgba(0.15, 0.15, 0.35, 0.8);
+const NORMAL_BUTTON: Color = Color::srgba(0.15, 0.15, 0.35, 0.8);
+const HOVER_BUTTON: Color = Color::srgba(0.25, 0.75, 0.95, 0.9);
+const TEXT_COLOR: Color = Color::srgb(0.9, 0.9, 0.95);
+const ACCENT_COLOR: Color = Color::srgb(0.0, 0.8, 1.0);
+const ERROR_COLOR: Color = Color::srgb(1.0, 0.4, 0.35);
+
+// 进度条尺寸
+const BAR_WIDTH: f32 = 480.0;
+const BAR_HEIGHT: f32 = 16.0;
+
+#[derive(Component)]
+struct LoadingBarFill;
+
+#[derive(Component)]
+struct LoadingText;
+
+#[derive(Component)]
+struct LoadingErrorText;
+
+#[derive(Component)]
+struct LoadingBackButton;
+
+///加载界面渲染，离开 Loading 时自动销毁
+fn spawn_loading_screen(mut commands: Commands) {
+    // 菜单相机已销毁、游戏相机尚未生成，加载界面需要自己的 UI 相机
+    commands.spawn((
+        Camera2d,
+        DespawnOnExit(AppState::Loading),
+        Name::new("Loading UI Camera"),
+    ));
+
+    commands
+        .spawn((
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                row_gap: Val::Px(16.0),
+                ..default()
+            },
+            BackgroundColor(BG_COLOR),
+            DespawnOnExit(AppState::Loading),
+            Name::new("Loading Screen"),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                LocalizedText::new("LOADING", "加载中"),
+                TextFont {
+                    font_size: 40.0,
+                    ..default()
+                },
+                TextColor(ACCENT_COLOR),
+            ));
+
+            // 进度条：底槽 + 按进度改变宽度的填充
+            parent
+                .spawn((
+                    Node {
+                        width: Val::Px(BAR_WIDTH),
+                        height: Val::Px(BAR_HEIGHT),
+                        ..default()
+                    },
+                    BackgroundColor(TRACK_COLOR),
+                    BorderRadius::all(Val::Px(BAR_HEIGHT / 2.0)),
+                ))
+                .with_child((
+                    Node {
+                        width: Val::Percent(0.0),
+                        height: Val::Percent(100.0),
+                        ..default()
+                    },
+                    BackgroundColor(ACCENT_COLOR),
+                    BorderRadius::all(Val::Px(BAR_HEIGHT / 2.0)),
+                    LoadingBarFill,
+                ));
+
+            parent.spawn((
+                Text::new(""),
+                TextFont {
+                    font_size: 20.0,
+                    ..default()
+                },
+                TextColor(TEXT_COLOR),
+                LoadingText,
+            ));
+
+            parent.spawn((
+                Text::new(""),
+                TextFont {
+                    font_size: 18.0,
+                    ..default()
+                },
+                TextColor(ERROR_COLOR),
+                Node {
+                    max_width: Val::Px(720.0),
+                    ..default()
+                },
+                LoadingErrorText,
+            ));
+
+            // 加载失败后才显示
+            parent
+                .spawn((
+                    Button,
+                    Node {
+                        width: Val::Px(240.0),
+                        height: Val::Px(56.0),
+                        justify_content: JustifyContent::Center,
+                        align_items: AlignItems::Center,
+                        display: Display::None,
+                        ..default()
+                    },
+                    BackgroundColor(NORMAL_BUTTON),
+                    BorderRadius::all(Val::Px(16.0)),
+                    LoadingBackButton,
+                ))
+                .with_child((
+                    LocalizedText::new("BACK TO MENU", "返回主菜单"),
+                    TextFont {
+                        font_size: 24.0,
+                        ..default()
+                    },
+                    TextColor(TEXT_COLOR),
+                ));
+        });
+}
+
+/// 按 AssetServer 的加载状态刷新进度条、计数与错误信息
+fn update_loading_screen(
+    asset_server: Res<AssetServer>,
+    loading: Res<LoadingAssets>,
+    mut fill: Single<&mut Node, With<LoadingBarFill>>,
+    mut text: Single<&mut Text, (With<LoadingText>, Without<LoadingErrorText>)>,
+    mut error_text: Single<&mut Text, (With<LoadingErrorText>, Without<LoadingText>)>,
+    mut back_button: Single<&mut Node, (With<LoadingBackButton>, Without<LoadingBarFill>)>,
+) {
+    let progress = loading.progress(&asset_server);
+    fill.width = Val::Percent(progress.fraction() * 100.0);
+    text.0 = format!("{} / {}", progress.loaded, progress.total);
+
+    if progress.failures.is_empty() {
+        return;
+    }
+    error_text.0 = progress
+        .failures
+        .iter()
+        .map(|failure| format!("FAILED: {}\n{}", failure.label, failure.error))
+        .collect::<Vec<_>>()
+        .join("\n");
+    back_button.display = Display::Flex;
+}
+
+/// 加载失败时返回主菜单
+#[allow(clippy::type_complexity)]
+fn loading_back_button(
+    mut interaction_query: Query<
+        (&Interaction, &mut BackgroundColor),
+        (Changed<Interaction>, With<LoadingBackButton>),
+    >,
+    mut next_app_state: ResMut<NextState<AppState>>,
+) {
+    for (interaction, mut bg) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => next_app_state.set(AppState::Menu),
+            Interaction::Hovered => *bg = HOVER_BUTTON.into(),
+            Interaction::None => *bg = NORMAL_BUTTON.into(),
+        }
+    }
+}
@@ -0,0 +1,42 @@
+///界面文字本地化：带 LocalizedText 的文本按 GameSettings 中的界面语言显示，在设置界面切换语言后立即刷新
+///主菜单、暂停菜单与加载界面的固定文字都通过它显示；设置界面切换语言时整体重建，直接使用 Language::pick
+use bevy::prelude::*;
+use tect_state::settings::GameSettings;
+
+pub struct LocalizedTextPlugin;
+
+impl Plugin for LocalizedTextPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, update_localized_text);
+    }
+}
+
+/// 同一段文字的英文与中文版本，Text 的内容由 update_localized_text 填写
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+#[require(Text)]
+pub struct LocalizedText {
+    pub english: &'static str,
+    pub chinese: &'static str,
+}
+
+impl LocalizedText {
+    pub fn new(english: &'static str, chinese: &'static str) -> Self {
+        Self { english, chinese }
+    }
+}
+
+/// 新生成的文本与语言变化时刷新
+fn update_localized_text(
+    settings: Res<GameSettings>,
+    mut texts: Query<(Ref<LocalizedText>, &mut Text)>,
+) {
+    let changed = settings.is_changed();
+    for (localized, mut text) in &mut texts {
+        if changed || localized.is_added() {
+            text.0 = settings
+                .language
+                .pick(localized.english, localized.chinese)
+                .to_string();
+        }
+    }
+}
@@ -1,6 +1,9 @@
-///主菜单界面
+///主菜单界面，按钮文字随设置中的界面语言切换
 use bevy::prelude::*;
 use tect_state::app_state::*;
+use tect_state::save::PendingLoad;
+
+use crate::localized_text::LocalizedText;
 
 pub struct MainUiPlugin;
 
@@ -13,7 +16,6 @@ impl Plugin for MainUiPlugin {
 }
 
 // ui_style.rs 或直接放在文件顶部
-const BG_COLOR: Color = Color::srgb(0.05, 0.05, 0.12);
 const PANEL_COLOR: Color = Color::srgba(0.1, 0.1, 0.2, 0.92);
 const NORMAL_BUTTON: Color = Color::srgba(0.15, 0.15, 0.35, 0.8);
 const HOVER_BUTTON: Color = Color::srgba(0.25, 0.75, 0.95, 0.9);
@@ -54,12 +56,11 @@ pub struct MenuBkCm {
 fn setup_menu(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
-    mut next_app_state: ResMut<NextState<AppState>>,
     mut next_menu_state: ResMut<NextState<MenuOptions>>,
 ) {
     //  生成菜单专用的 2D UI 相机（
     commands.spawn((
-        Camera2d::default(),
+        Camera2d,
         Camera {
             // 确保在所有 3D 相机之上
             order: 999,
@@ -79,19 +80,20 @@ fn setup_menu(
                 position_type: PositionType::Absolute,
                 ..default()
             },
-            // BackgroundColor(BG_COLOR),
             Sprite {
                 image: asset_server.load("ui_image/BG2.png"),
                 ..default()
             },
             Name::new("Menu Root"),
+            // 离开主菜单时连同背景一起销毁，从游戏返回时重新生成
+            DespawnOnExit(AppState::Menu),
         ))
         .with_children(|parent| {
             // 标题
             // parent.spawn((
             //     Text::new("MY AWESOME GAME"),
             //     TextFont {
-            //         font: asset_server.load("fonts/AlibabaPuHuiTi-3-55-Regular.ttf"), 
+            //         font: asset_server.load("fonts/AlibabaPuHuiTi-3-55-Regular.ttf"),
             //         font_size: 80.0,
             //         ..default()
             //     },
@@ -124,12 +126,27 @@ fn setup_menu(
                 ))
                 .with_children(|panel| {
                     let options = [
-                        ("NEW GAME", MenuButtonAction::NewGame),
-                        ("CONTINUE", MenuButtonAction::ContinueGame),
-                        ("ONLINE", MenuButtonAction::OnlineGame),
-                        ("SETTINGS", MenuButtonAction::OpenSettings),
-                        ("ABOUT", MenuButtonAction::OpenAbout),
-                        ("QUIT", MenuButtonAction::Quit),
+                        (
+                            LocalizedText::new("NEW GAME", "新游戏"),
+                            MenuButtonAction::NewGame,
+                        ),
+                        (
+                            LocalizedText::new("CONTINUE", "继续游戏"),
+                            MenuButtonAction::ContinueGame,
+                        ),
+                        (
+                            LocalizedText::new("ONLINE", "联机游戏"),
+                            MenuButtonAction::OnlineGame,
+                        ),
+                        (
+                            LocalizedText::new("SETTINGS", "设置"),
+                            MenuButtonAction::OpenSettings,
+                        ),
+                        (
+                            LocalizedText::new("ABOUT", "关于"),
+                            MenuButtonAction::OpenAbout,
+                        ),
+                        (LocalizedText::new("QUIT", "退出"), MenuButtonAction::Quit),
                     ];
 
                     for (label, action) in options {
@@ -151,7 +168,7 @@ fn setup_menu(
                                 action,
                             ))
                             .with_child((
-                                Text::new(label),
+                                label,
                                 TextFont {
                                     // font: asset_server.load("fonts/AlibabaPuHuiTi-3-55-Regular"),
                                     font_size: 32.0,
@@ -168,6 +185,7 @@ fn setup_menu(
 }
 
 ///按钮点击逻辑
+#[allow(clippy::type_complexity)]
 fn menu_button_system(
     mut interaction_query: Query<
         (
@@ -180,6 +198,7 @@ fn menu_button_system(
     >,
     mut next_app_state: ResMut<NextState<AppState>>,
     mut next_menu_state: ResMut<NextState<MenuOptions>>,
+    mut pending_load: ResMut<PendingLoad>,
     mut exit: MessageWriter<AppExit>,
 ) {
     for (interaction, action, mut bg, mut border) in &mut interaction_query {
@@ -190,11 +209,12 @@ fn menu_button_system(
 
                 match action {
                     MenuButtonAction::NewGame => {
-                        next_app_state.set(AppState::InGame);
+                        *pending_load = PendingLoad::NewGame;
+                        next_app_state.set(AppState::Loading);
                     }
                     MenuButtonAction::ContinueGame => {
-                        // 加载存档逻辑
-                        next_app_state.set(AppState::InGame);
+                        // 打开读档列表，选择槽位后进入游戏
+                        next_menu_state.set(MenuOptions::ContinueGame);
                     }
                     MenuButtonAction::OnlineGame => {
                         next_menu_state.set(MenuOptions::OnlineGame);
@@ -222,8 +242,6 @@ fn menu_button_system(
     }
 }
 
-
-
 // ────────────────────────────── 退出菜单：清除 UI + 相机 ──────────────────────────────
 fn cleanup_menu(
     mut commands: Commands,
@@ -242,4 +260,4 @@ fn cleanup_menu(
 
     // 可选：也清除背景图（如果你想更干净）
     // commands.entity(background).despawn();
-}
\ No newline at end of file
+}
@@ -0,0 +1,308 @@
+///小地图：俯视的正交相机把地图渲染到纹理，显示在屏幕右下角
+///单位显示为圆点（选中的高亮），主相机的视野显示为梯形；左键点击或拖动移动相机焦点，右键向选中单位下达移动命令
+use bevy::camera::visibility::RenderLayers;
+use bevy::camera::{RenderTarget, ScalingMode};
+use bevy::prelude::*;
+use bevy::render::render_resource::TextureFormat;
+use bevy::ui::RelativeCursorPosition;
+use std::f32::consts::FRAC_PI_2;
+use tect_camera::follow::CameraFollow;
+use tect_camera::god_view_camera::GodViewCamera;
+use tect_camera::settings::CameraBounds;
+use tect_control::moving::PlayerMove;
+use tect_control::orders::{CommandSelectedUnits, OrderKind};
+use tect_control::raycast::GroundRaycast;
+use tect_control::selection::Selected;
+use tect_state::app_state::*;
+use tect_state::input::{ActionState, InputAction};
+
+pub struct MinimapPlugin;
+
+impl Plugin for MinimapPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_gizmo_config(
+            MinimapGizmos,
+            GizmoConfig {
+                line: GizmoLineConfig {
+                    width: 2.0,
+                    ..default()
+                },
+                render_layers: RenderLayers::layer(MINIMAP_LAYER),
+                ..default()
+            },
+        )
+        .add_systems(OnEnter(AppState::InGame), spawn_minimap)
+        .add_systems(
+            Update,
+            (
+                fit_minimap_camera,
+                minimap_input.run_if(in_state(InGameState::Playing)),
+                draw_minimap_overlay,
+            )
+                .run_if(in_state(AppState::InGame))
+                .chain(),
+        );
+    }
+}
+
+// 小地图专用的渲染层，标记只在小地图上显示（主相机看不到）
+const MINIMAP_LAYER: usize = 1;
+// 小地图纹理分辨率与屏幕上的尺寸
+const MINIMAP_RESOLUTION: u32 = 256;
+const MINIMAP_SIZE: f32 = 220.0;
+const MINIMAP_MARGIN: f32 = 12.0;
+// 俯视相机的高度，标记画在略低于相机的位置，保证不被场景遮挡
+const MINIMAP_HEIGHT: f32 = 200.0;
+const OVERLAY_HEIGHT: f32 = MINIMAP_HEIGHT - 1.0;
+// 还没有地图边界时显示的范围（米）
+const DEFAULT_EXTENT: f32 = 100.0;
+// 单位圆点半径，按小地图覆盖范围的比例
+const UNIT_DOT_SCALE: f32 = 0.012;
+
+const UNIT_COLOR: Color = Color::srgb(0.9, 0.9, 0.95);
+const SELECTED_UNIT_COLOR: Color = Color::srgb(0.3, 1.0, 0.4);
+const FRUSTUM_COLOR: Color = Color::srgb(1.0, 1.0, 1.0);
+const ACCENT_COLOR: Color = Color::srgb(0.0, 0.8, 1.0);
+
+/// 只渲染到小地图的 Gizmos
+#[derive(Default, Reflect, GizmoConfigGroup)]
+pub struct MinimapGizmos;
+
+/// 小地图俯视相机
+#[derive(Component)]
+pub struct MinimapCamera;
+
+/// 小地图 UI 节点
+#[derive(Component)]
+pub struct Minimap;
+
+/// 小地图覆盖的 XZ 平面范围：以 center 为中心、边长为 size 的正方形
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct MinimapView {
+    center: Vec2,
+    size: f32,
+}
+
+impl MinimapView {
+    fn from_bounds(bounds: &CameraBounds) -> Self {
+        match bounds.0 {
+            Some(rect) => Self {
+                center: rect.center(),
+                size: rect.width().max(rect.height()).max(1.0),
+            },
+            None => Self {
+                center: Vec2::ZERO,
+                size: DEFAULT_EXTENT,
+            },
+        }
+    }
+
+    /// 小地图上的相对坐标（中心为 0，右下角为 (0.5, 0.5)）转为世界 XZ 坐标
+    fn to_world(self, normalized: Vec2) -> Vec2 {
+        self.center + normalized * self.size
+    }
+}
+
+// ──────────────────────────────────────────────────────────────
+// 1. 生成俯视相机与 UI
+// ──────────────────────────────────────────────────────────────
+fn spawn_minimap(
+    mut commands: Commands,
+    mut images: ResMut<Assets<Image>>,
+    bounds: Res<CameraBounds>,
+) {
+    let image = images.add(Image::new_target_texture(
+        MINIMAP_RESOLUTION,
+        MINIMAP_RESOLUTION,
+        TextureFormat::Bgra8UnormSrgb,
+    ));
+    let view = MinimapView::from_bounds(&bounds);
+
+    commands.spawn((
+        Camera3d::default(),
+        Camera {
+            target: RenderTarget::Image(image.clone().into()),
+            // 先于主相机渲染
+            order: -1,
+            clear_color: ClearColorConfig::Custom(Color::srgb(0.05, 0.05, 0.12)),
+            ..default()
+        },
+        Projection::from(OrthographicProjection {
+            scaling_mode: ScalingMode::Fixed {
+                width: view.size,
+                height: view.size,
+            },
+            far: MINIMAP_HEIGHT * 2.0,
+            ..OrthographicProjection::default_3d()
+        }),
+        minimap_camera_transform(view),
+        AmbientLight {
+            brightness: 1000.0,
+            ..default()
+        },
+        RenderLayers::from_layers(&[0, MINIMAP_LAYER]),
+        MinimapCamera,
+        DespawnOnExit(AppState::InGame),
+        Name::new("Minimap Camera"),
+    ));
+
+    commands.spawn((
+        ImageNode::new(image),
+        Node {
+            position_type: PositionType::Absolute,
+            right: Val::Px(MINIMAP_MARGIN),
+            bottom: Val::Px(MINIMAP_MARGIN),
+            width: Val::Px(MINIMAP_SIZE),
+            height: Val::Px(MINIMAP_SIZE),
+            border: UiRect::all(Val::Px(2.0)),
+            ..default()
+        },
+        BorderColor::all(ACCENT_COLOR.with_alpha(0.6)),
+        Interaction::default(),
+        RelativeCursorPosition::default(),
+        Minimap,
+        DespawnOnExit(AppState::InGame),
+        Name::new("Minimap"),
+    ));
+}
+
+/// 正上方俯视，画面上方为 -Z，与主相机默认朝向一致
+fn minimap_camera_transform(view: MinimapView) -> Transform {
+    let center = Vec3::new(view.center.x, 0.0, view.center.y);
+    Transform::from_translation(center.with_y(MINIMAP_HEIGHT)).looking_at(center, Vec3::NEG_Z)
+}
+
+// ──────────────────────────────────────────────────────────────
+// 2. 地图边界确定后让俯视相机覆盖整张地图
+// ──────────────────────────────────────────────────────────────
+fn fit_minimap_camera(
+    bounds: Res<CameraBounds>,
+    mut cameras: Query<(&mut Transform, &mut Projection), With<MinimapCamera>>,
+) {
+    if !bounds.is_changed() {
+        return;
+    }
+    let view = MinimapView::from_bounds(&bounds);
+    for (mut transform, mut projection) in cameras.iter_mut() {
+        *transform = minimap_camera_transform(view);
+        if let Projection::Orthographic(ortho) = projection.as_mut() {
+            ortho.scaling_mode = ScalingMode::Fixed {
+                width: view.size,
+                height: view.size,
+            };
+        }
+    }
+}
+
+// ──────────────────────────────────────────────────────────────
+// 3. 点击小地图：左键移动焦点，右键下达移动命令
+// ──────────────────────────────────────────────────────────────
+#[allow(clippy::too_many_arguments)]
+fn minimap_input(
+    actions: Res<ActionState>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    bounds: Res<CameraBounds>,
+    minimap: Single<(&Interaction, &RelativeCursorPosition), With<Minimap>>,
+    mut follow: ResMut<CameraFollow>,
+    mut camera_query: Query<&mut GodViewCamera>,
+    mut ground_raycast: GroundRaycast,
+    mut unit_commands: MessageWriter<CommandSelectedUnits>,
+    mut dragging: Local<bool>,
+) {
+    let (interaction, cursor) = *minimap;
+    let hovered = *interaction != Interaction::None;
+    let view = MinimapView::from_bounds(&bounds);
+    let point = cursor
+        .normalized
+        .filter(|_| cursor.cursor_over())
+        .map(|normalized| view.to_world(normalized));
+
+    // 左键在小地图上按下后，按住拖动持续移动焦点
+    if actions.just_pressed(InputAction::Select) {
+        *dragging = hovered;
+    }
+    if !actions.pressed(InputAction::Select) {
+        *dragging = false;
+    }
+    if *dragging
+        && let Some(point) = point
+        && let Ok(mut camera) = camera_query.single_mut()
+    {
+        follow.release();
+        camera.focus = Vec3::new(point.x, camera.focus.y, point.y);
+    }
+
+    if hovered
+        && actions.just_pressed(InputAction::MoveCommand)
+        && let Some(point) = point
+    {
+        // 从高处向下找地面高度，找不到时退回到 y = 0
+        let target = ground_raycast
+            .cast_down(point.x, point.y, MINIMAP_HEIGHT)
+            .map_or(Vec3::new(point.x, 0.0, point.y), |hit| hit.point);
+        unit_commands.write(CommandSelectedUnits {
+            target,
+            kind: OrderKind::from_modifiers(&keyboard),
+            append: keyboard.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]),
+        });
+    }
+}
+
+// ──────────────────────────────────────────────────────────────
+// 4. 单位圆点与主相机视野梯形
+// ──────────────────────────────────────────────────────────────
+fn draw_minimap_overlay(
+    mut gizmos: Gizmos<MinimapGizmos>,
+    bounds: Res<CameraBounds>,
+    units: Query<(&GlobalTransform, Has<Selected>), With<PlayerMove>>,
+    main_camera: Query<(&Camera, &GlobalTransform, &GodViewCamera)>,
+) {
+    let view = MinimapView::from_bounds(&bounds);
+    let facing_up = Quat::from_rotation_x(FRAC_PI_2);
+
+    let radius = view.size * UNIT_DOT_SCALE;
+    for (transform, selected) in units.iter() {
+        let position = transform.translation().with_y(OVERLAY_HEIGHT);
+        let color = if selected {
+            SELECTED_UNIT_COLOR
+        } else {
+            UNIT_COLOR
+        };
+        // 同心圆近似实心圆点
+        for scale in [1.0, 0.66, 0.33] {
+            gizmos.circle(Isometry3d::new(position, facing_up), radius * scale, color);
+        }
+    }
+
+    let Ok((camera, camera_transform, god_view)) = main_camera.single() else {
+        return;
+    };
+    let Some(viewport) = camera.logical_viewport_size() else {
+        return;
+    };
+    // 屏幕四角的射线与焦点所在的水平面求交；朝向天空的射线截断在小地图范围处
+    let ground = Vec3::Y * god_view.current.focus.y;
+    let corners = [
+        Vec2::ZERO,
+        Vec2::new(viewport.x, 0.0),
+        viewport,
+        Vec2::new(0.0, viewport.y),
+    ]
+    .map(|corner| {
+        let Ok(ray) = camera.viewport_to_world(camera_transform, corner) else {
+            return ground;
+        };
+        let distance = ray
+            .intersect_plane(ground, InfinitePlane3d::new(Vec3::Y))
+            .unwrap_or(f32::INFINITY)
+            .min(view.size);
+        ray.get_point(distance)
+    });
+    gizmos.linestrip(
+        corners
+            .iter()
+            .chain(corners.first())
+            .map(|corner| corner.with_y(OVERLAY_HEIGHT)),
+        FRUSTUM_COLOR,
+    );
+}
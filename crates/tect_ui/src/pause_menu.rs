@@ -0,0 +1,222 @@
+///暂停菜单：游戏中按暂停键（默认 Esc）打开，期间 Time<Virtual> 冻结，单位移动、动画与相机全部停止
+///提供继续游戏、保存与读取（存档列表）、设置（与主菜单共用设置面板）与返回主菜单；返回主菜单时 world_map 生成的实体随 InGame 状态一起销毁
+use bevy::prelude::*;
+use bevy::ui::FocusPolicy;
+use tect_state::app_state::*;
+use tect_state::input::{ActionState, InputAction};
+
+use crate::localized_text::LocalizedText;
+use crate::save_slots::{spawn_save_slot_panel, SaveSlotMode, SaveSlotPanel};
+use crate::settings_menu::{spawn_settings_panel, SettingsPanel};
+
+pub struct PauseMenuPlugin;
+
+impl Plugin for PauseMenuPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(InGameState::Paused), spawn_pause_menu)
+            .add_systems(
+                Update,
+                (
+                    toggle_pause,
+                    pause_button_system.run_if(in_state(InGameState::Paused)),
+                )
+                    .run_if(in_state(AppState::InGame))
+                    .chain(),
+            );
+    }
+}
+
+const BACKDROP_COLOR: Color = Color::srgba(0.0, 0.0, 0.0, 0.55);
+const PANEL_COLOR: Color = Color::srgba(0.1, 0.1, 0.2, 0.92);
+const NORMAL_BUTTON: Color = Color::srgba(0.15, 0.15, 0.35, 0.8);
+const HOVER_BUTTON: Color = Color::srgba(0.25, 0.75, 0.95, 0.9);
+const PRESSED_BUTTON: Color = Color::srgba(0.35, 0.85, 1.0, 1.0);
+const TEXT_COLOR: Color = Color::srgb(0.9, 0.9, 0.95);
+const ACCENT_COLOR: Color = Color::srgb(0.0, 0.8, 1.0);
+
+//暂停菜单按钮标记
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PauseButtonAction {
+    Resume,
+    Save,
+    Load,
+    OpenSettings,
+    QuitToMenu,
+}
+
+/// 暂停键切换暂停 / 继续
+fn toggle_pause(
+    actions: Res<ActionState>,
+    state: Res<State<InGameState>>,
+    mut next_state: ResMut<NextState<InGameState>>,
+) {
+    if !actions.just_pressed(InputAction::Pause) {
+        return;
+    }
+    next_state.set(match state.get() {
+        InGameState::Playing => InGameState::Paused,
+        InGameState::Paused => InGameState::Playing,
+    });
+}
+
+///暂停菜单渲染，离开 Paused 时自动销毁
+fn spawn_pause_menu(mut commands: Commands) {
+    // 全屏半透明遮罩，挡住下方的小地图等 UI
+    commands
+        .spawn((
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                position_type: PositionType::Absolute,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(BACKDROP_COLOR),
+            FocusPolicy::Block,
+            GlobalZIndex(10),
+            DespawnOnExit(InGameState::Paused),
+            Name::new("Pause Menu"),
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn((
+                    Node {
+                        width: Val::Px(360.0),
+                        flex_direction: FlexDirection::Column,
+                        justify_content: JustifyContent::Center,
+                        align_items: AlignItems::Center,
+                        padding: UiRect::all(Val::Px(32.0)),
+                        border: UiRect::all(Val::Px(2.0)),
+                        ..default()
+                    },
+                    BackgroundColor(PANEL_COLOR),
+                    BorderRadius::all(Val::Px(24.0)),
+                    BorderColor::all(ACCENT_COLOR.with_alpha(0.3)),
+                ))
+                .with_children(|panel| {
+                    panel.spawn((
+                        LocalizedText::new("PAUSED", "已暂停"),
+                        TextFont {
+                            font_size: 40.0,
+                            ..default()
+                        },
+                        TextColor(ACCENT_COLOR),
+                        Node {
+                            margin: UiRect::bottom(Val::Px(16.0)),
+                            ..default()
+                        },
+                    ));
+
+                    let options = [
+                        (
+                            LocalizedText::new("RESUME", "继续游戏"),
+                            PauseButtonAction::Resume,
+                        ),
+                        (LocalizedText::new("SAVE", "保存"), PauseButtonAction::Save),
+                        (LocalizedText::new("LOAD", "读取"), PauseButtonAction::Load),
+                        (
+                            LocalizedText::new("SETTINGS", "设置"),
+                            PauseButtonAction::OpenSettings,
+                        ),
+                        (
+                            LocalizedText::new("QUIT TO MENU", "返回主菜单"),
+                            PauseButtonAction::QuitToMenu,
+                        ),
+                    ];
+
+                    for (label, action) in options {
+                        panel
+                            .spawn((
+                                Button,
+                                Node {
+                                    width: Val::Percent(100.0),
+                                    height: Val::Px(56.0),
+                                    margin: UiRect::vertical(Val::Px(8.0)),
+                                    justify_content: JustifyContent::Center,
+                                    align_items: AlignItems::Center,
+                                    ..default()
+                                },
+                                BackgroundColor(NORMAL_BUTTON),
+                                BorderRadius::all(Val::Px(16.0)),
+                                BorderColor::all(ACCENT_COLOR.with_alpha(0.4)),
+                                action,
+                            ))
+                            .with_child((
+                                label,
+                                TextFont {
+                                    font_size: 28.0,
+                                    ..default()
+                                },
+                                TextColor(TEXT_COLOR),
+                            ));
+                    }
+                });
+        });
+}
+
+///按钮点击逻辑
+#[allow(clippy::type_complexity)]
+fn pause_button_system(
+    mut commands: Commands,
+    mut interaction_query: Query<
+        (
+            &Interaction,
+            &PauseButtonAction,
+            &mut BackgroundColor,
+            &mut BorderColor,
+        ),
+        (Changed<Interaction>, With<Button>),
+    >,
+    mut next_app_state: ResMut<NextState<AppState>>,
+    mut next_game_state: ResMut<NextState<InGameState>>,
+    settings_panels: Query<(), With<SettingsPanel>>,
+    save_slot_panels: Query<(), With<SaveSlotPanel>>,
+) {
+    for (interaction, action, mut bg, mut border) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                *bg = PRESSED_BUTTON.into();
+                border.set_all(ACCENT_COLOR);
+
+                match action {
+                    PauseButtonAction::Resume => {
+                        next_game_state.set(InGameState::Playing);
+                    }
+                    PauseButtonAction::Save | PauseButtonAction::Load => {
+                        // 存档列表盖在暂停菜单之上，继续游戏时一起销毁
+                        if save_slot_panels.is_empty() {
+                            let mode = if *action == PauseButtonAction::Save {
+                                SaveSlotMode::Save
+                            } else {
+                                SaveSlotMode::Load
+                            };
+                            spawn_save_slot_panel(
+                                &mut commands,
+                                mode,
+                                DespawnOnExit(InGameState::Paused),
+                            );
+                        }
+                    }
+                    PauseButtonAction::OpenSettings => {
+                        // 设置面板盖在暂停菜单之上，继续游戏时一起销毁
+                        if settings_panels.is_empty() {
+                            spawn_settings_panel(&mut commands, DespawnOnExit(InGameState::Paused));
+                        }
+                    }
+                    PauseButtonAction::QuitToMenu => {
+                        next_app_state.set(AppState::Menu);
+                    }
+                }
+            }
+            Interaction::Hovered => {
+                *bg = HOVER_BUTTON.into();
+                border.set_all(ACCENT_COLOR);
+            }
+            Interaction::None => {
+                *bg = NORMAL_BUTTON.into();
+                border.set_all(ACCENT_COLOR.with_alpha(0.4));
+            }
+        }
+    }
+}
@@ -0,0 +1,299 @@
+///存档列表：主菜单的 CONTINUE（MenuOptions::ContinueGame）打开读档列表，暂停菜单的保存 / 读取打开对应列表
+///每个槽位显示地图与保存时间；保存写入 SaveGame 消息，读档设置 PendingLoad 后进入 Loading；自动存档槽位只能读取
+use bevy::prelude::*;
+use bevy::ui::FocusPolicy;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tect_state::app_state::*;
+use tect_state::save::{PendingLoad, SaveGame, SaveSlot, SaveSlots};
+use tect_state::settings::{GameSettings, Language};
+
+pub struct SaveSlotsPlugin;
+
+impl Plugin for SaveSlotsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(MenuOptions::ContinueGame), open_load_from_menu)
+            .add_systems(
+                Update,
+                (save_slot_button_system, rebuild_save_slot_panel).chain(),
+            );
+    }
+}
+
+const BACKDROP_COLOR: Color = Color::srgba(0.0, 0.0, 0.0, 0.55);
+const PANEL_COLOR: Color = Color::srgba(0.1, 0.1, 0.2, 0.96);
+const NORMAL_BUTTON: Color = Color::srgba(0.15, 0.15, 0.35, 0.8);
+const HOVER_BUTTON: Color = Color::srgba(0.25, 0.75, 0.95, 0.9);
+const PRESSED_BUTTON: Color = Color::srgba(0.35, 0.85, 1.0, 1.0);
+const EMPTY_SLOT: Color = Color::srgba(0.12, 0.12, 0.2, 0.6);
+const TEXT_COLOR: Color = Color::srgb(0.9, 0.9, 0.95);
+const DIM_TEXT_COLOR: Color = Color::srgb(0.5, 0.5, 0.6);
+const ACCENT_COLOR: Color = Color::srgb(0.0, 0.8, 1.0);
+
+// ──────────────────────────────────────────────────────────────
+// 1. 面板与按钮
+// ──────────────────────────────────────────────────────────────
+
+/// 存档列表的用途
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SaveSlotMode {
+    Save,
+    Load,
+}
+
+/// 存档列表的根节点；built 为 false 时在下一帧重建内容
+#[derive(Component, Debug)]
+pub struct SaveSlotPanel {
+    mode: SaveSlotMode,
+    built: bool,
+}
+
+/// 按钮的作用
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+enum SaveSlotButton {
+    Save(SaveSlot),
+    Load(SaveSlot),
+    Close,
+}
+
+fn slot_label(slot: SaveSlot, language: Language) -> String {
+    match slot {
+        SaveSlot::Manual(index) => format!("{} {index}", language.pick("SLOT", "存档")),
+        SaveSlot::Quick => language.pick("QUICK SAVE", "快速存档").into(),
+        SaveSlot::Auto => language.pick("AUTOSAVE", "自动存档").into(),
+    }
+}
+
+/// 保存时间距今多久
+fn age_label(saved_at: u64, language: Language) -> String {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs());
+    let minutes = now.saturating_sub(saved_at) / 60;
+    match (minutes, language) {
+        (0, _) => language.pick("just now", "刚刚").into(),
+        (1..60, Language::English) => format!("{minutes} min ago"),
+        (1..60, Language::Chinese) => format!("{minutes} 分钟前"),
+        (60..1440, Language::English) => format!("{} h ago", minutes / 60),
+        (60..1440, Language::Chinese) => format!("{} 小时前", minutes / 60),
+        (_, Language::English) => format!("{} days ago", minutes / 1440),
+        (_, Language::Chinese) => format!("{} 天前", minutes / 1440),
+    }
+}
+
+// ──────────────────────────────────────────────────────────────
+// 2. 打开与关闭
+// ──────────────────────────────────────────────────────────────
+
+/// 生成存档列表，scope 决定面板何时随状态销毁（例如 DespawnOnExit(InGameState::Paused)）
+pub fn spawn_save_slot_panel(commands: &mut Commands, mode: SaveSlotMode, scope: impl Bundle) {
+    commands.spawn((
+        Node {
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            position_type: PositionType::Absolute,
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        BackgroundColor(BACKDROP_COLOR),
+        FocusPolicy::Block,
+        GlobalZIndex(20),
+        SaveSlotPanel { mode, built: false },
+        scope,
+        Name::new("Save Slots"),
+    ));
+}
+
+fn open_load_from_menu(mut commands: Commands) {
+    spawn_save_slot_panel(
+        &mut commands,
+        SaveSlotMode::Load,
+        DespawnOnExit(MenuOptions::ContinueGame),
+    );
+}
+
+// ──────────────────────────────────────────────────────────────
+// 3. 面板内容
+// ──────────────────────────────────────────────────────────────
+
+/// 面板刚生成、存档变化或切换语言时重建内容
+fn rebuild_save_slot_panel(
+    mut commands: Commands,
+    slots: Res<SaveSlots>,
+    settings: Res<GameSettings>,
+    mut panels: Query<(Entity, &mut SaveSlotPanel)>,
+) {
+    let changed = slots.is_changed() || settings.is_changed();
+    for (entity, mut panel) in &mut panels {
+        if panel.built && !changed {
+            continue;
+        }
+        panel.built = true;
+        let mode = panel.mode;
+        commands
+            .entity(entity)
+            .despawn_children()
+            .with_children(|parent| build_panel(parent, mode, &slots, settings.language));
+    }
+}
+
+fn build_panel(
+    parent: &mut ChildSpawnerCommands,
+    mode: SaveSlotMode,
+    slots: &SaveSlots,
+    language: Language,
+) {
+    parent
+        .spawn((
+            Node {
+                width: Val::Px(520.0),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Stretch,
+                padding: UiRect::all(Val::Px(28.0)),
+                row_gap: Val::Px(8.0),
+                border: UiRect::all(Val::Px(2.0)),
+                ..default()
+            },
+            BackgroundColor(PANEL_COLOR),
+            BorderRadius::all(Val::Px(24.0)),
+            BorderColor::all(ACCENT_COLOR.with_alpha(0.3)),
+        ))
+        .with_children(|panel| {
+            let title = match mode {
+                SaveSlotMode::Save => language.pick("SAVE GAME", "保存游戏"),
+                SaveSlotMode::Load => language.pick("LOAD GAME", "读取存档"),
+            };
+            panel.spawn((
+                Text::new(title),
+                TextFont {
+                    font_size: 36.0,
+                    ..default()
+                },
+                TextColor(ACCENT_COLOR),
+            ));
+
+            for slot in SaveSlot::all() {
+                let info = slots.0.get(&slot);
+                let button = match mode {
+                    // 自动存档只由定时器写入
+                    SaveSlotMode::Save if slot == SaveSlot::Auto => continue,
+                    SaveSlotMode::Save => Some(SaveSlotButton::Save(slot)),
+                    SaveSlotMode::Load => info.map(|_| SaveSlotButton::Load(slot)),
+                };
+                let detail = match info {
+                    Some(info) => {
+                        format!("{}  ·  {}", info.map, age_label(info.saved_at, language))
+                    }
+                    None => language.pick("Empty", "空").into(),
+                };
+                spawn_slot_row(panel, slot_label(slot, language), detail, button);
+            }
+
+            spawn_slot_row(
+                panel,
+                language.pick("BACK", "返回").into(),
+                String::new(),
+                Some(SaveSlotButton::Close),
+            );
+        });
+}
+
+/// 槽位名称 + 存档概要；没有按钮的行（读档时的空槽位）显示为灰色
+fn spawn_slot_row(
+    parent: &mut ChildSpawnerCommands,
+    label: String,
+    detail: String,
+    button: Option<SaveSlotButton>,
+) {
+    let (color, text_color) = match button {
+        Some(_) => (NORMAL_BUTTON, TEXT_COLOR),
+        None => (EMPTY_SLOT, DIM_TEXT_COLOR),
+    };
+    let mut row = parent.spawn((
+        Node {
+            height: Val::Px(48.0),
+            padding: UiRect::horizontal(Val::Px(16.0)),
+            justify_content: JustifyContent::SpaceBetween,
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        BackgroundColor(color),
+        BorderRadius::all(Val::Px(12.0)),
+    ));
+    if let Some(button) = button {
+        row.insert((Button, button));
+    }
+    row.with_children(|row| {
+        row.spawn((
+            Text::new(label),
+            TextFont {
+                font_size: 22.0,
+                ..default()
+            },
+            TextColor(text_color),
+        ));
+        row.spawn((
+            Text::new(detail),
+            TextFont {
+                font_size: 16.0,
+                ..default()
+            },
+            TextColor(text_color),
+        ));
+    });
+}
+
+// ──────────────────────────────────────────────────────────────
+// 4. 交互
+// ──────────────────────────────────────────────────────────────
+
+///按钮点击逻辑：保存后关闭列表，读档时离开当前状态进入 Loading
+#[allow(clippy::too_many_arguments)]
+fn save_slot_button_system(
+    mut commands: Commands,
+    mut interaction_query: Query<
+        (&Interaction, &SaveSlotButton, &mut BackgroundColor),
+        Changed<Interaction>,
+    >,
+    panels: Query<Entity, With<SaveSlotPanel>>,
+    app_state: Res<State<AppState>>,
+    mut pending_load: ResMut<PendingLoad>,
+    mut saves: MessageWriter<SaveGame>,
+    mut next_app_state: ResMut<NextState<AppState>>,
+    mut next_menu_state: ResMut<NextState<MenuOptions>>,
+) {
+    for (interaction, button, mut bg) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => *bg = PRESSED_BUTTON.into(),
+            Interaction::Hovered => {
+                *bg = HOVER_BUTTON.into();
+                continue;
+            }
+            Interaction::None => {
+                *bg = NORMAL_BUTTON.into();
+                continue;
+            }
+        }
+
+        match *button {
+            SaveSlotButton::Save(slot) => {
+                // 暂停期间存档系统照常运行
+                saves.write(SaveGame(slot));
+            }
+            SaveSlotButton::Load(slot) => {
+                *pending_load = PendingLoad::Slot(slot);
+                next_app_state.set(AppState::Loading);
+                continue;
+            }
+            SaveSlotButton::Close => {}
+        }
+
+        if *app_state.get() == AppState::Menu {
+            next_menu_state.set(MenuOptions::NewGame);
+        } else {
+            for panel in &panels {
+                commands.entity(panel).despawn();
+            }
+        }
+    }
+}
@@ -0,0 +1,612 @@
+///设置界面：主菜单的 SETTINGS（MenuOptions::Setting）与暂停菜单共用，分为图形、音频、操作与语言四个标签页
+///修改立即写入 GameSettings 并保存到 config/settings.ron，由各模块实时应用；操作页可对 InputAction 改键
+use bevy::prelude::*;
+use bevy::ui::FocusPolicy;
+use tect_state::app_state::*;
+use tect_state::input::{InputAction, InputBinding, InputMap, RebindState, INPUT_MAP_PATH};
+use tect_state::settings::{
+    GameSettings, Language, ShadowQuality, SoundChannel, WindowModeSetting, RESOLUTIONS,
+};
+
+pub struct SettingsMenuPlugin;
+
+impl Plugin for SettingsMenuPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SettingsTab>()
+            .add_systems(OnEnter(MenuOptions::Setting), open_settings_from_menu)
+            .add_systems(
+                Update,
+                (
+                    settings_button_system,
+                    rebuild_settings_panel,
+                    refresh_setting_values,
+                )
+                    .chain(),
+            );
+    }
+}
+
+const BACKDROP_COLOR: Color = Color::srgba(0.0, 0.0, 0.0, 0.55);
+const PANEL_COLOR: Color = Color::srgba(0.1, 0.1, 0.2, 0.96);
+const NORMAL_BUTTON: Color = Color::srgba(0.15, 0.15, 0.35, 0.8);
+const HOVER_BUTTON: Color = Color::srgba(0.25, 0.75, 0.95, 0.9);
+const PRESSED_BUTTON: Color = Color::srgba(0.35, 0.85, 1.0, 1.0);
+const TEXT_COLOR: Color = Color::srgb(0.9, 0.9, 0.95);
+const ACCENT_COLOR: Color = Color::srgb(0.0, 0.8, 1.0);
+
+// 音量与灵敏度每次点击的调整量
+const VOLUME_STEP: f32 = 0.1;
+const SENSITIVITY_STEP: f32 = 0.001;
+const SENSITIVITY_RANGE: (f32, f32) = (0.001, 0.02);
+
+// ──────────────────────────────────────────────────────────────
+// 1. 标签页与控件
+// ──────────────────────────────────────────────────────────────
+
+/// 当前显示的标签页
+#[derive(Resource, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SettingsTab {
+    #[default]
+    Graphics,
+    Audio,
+    Controls,
+    Language,
+}
+
+impl SettingsTab {
+    const ALL: [SettingsTab; 4] = [
+        SettingsTab::Graphics,
+        SettingsTab::Audio,
+        SettingsTab::Controls,
+        SettingsTab::Language,
+    ];
+
+    fn label(self, language: Language) -> &'static str {
+        match self {
+            SettingsTab::Graphics => language.pick("GRAPHICS", "图形"),
+            SettingsTab::Audio => language.pick("AUDIO", "音频"),
+            SettingsTab::Controls => language.pick("CONTROLS", "操作"),
+            SettingsTab::Language => language.pick("LANGUAGE", "语言"),
+        }
+    }
+}
+
+/// 设置面板的根节点；built 记录当前内容对应的标签页与语言，变化时重建
+#[derive(Component, Debug, Default)]
+pub struct SettingsPanel {
+    built: Option<(SettingsTab, Language)>,
+}
+
+/// 一个设置项
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SettingField {
+    Resolution,
+    WindowMode,
+    VSync,
+    Shadows,
+    MasterVolume,
+    Volume(SoundChannel),
+    Sensitivity,
+    EdgePan,
+    Language,
+    Binding(InputAction),
+}
+
+impl SettingField {
+    fn label(self, language: Language) -> String {
+        match self {
+            SettingField::Resolution => language.pick("Resolution", "分辨率").into(),
+            SettingField::WindowMode => language.pick("Window Mode", "窗口模式").into(),
+            SettingField::VSync => language.pick("VSync", "垂直同步").into(),
+            SettingField::Shadows => language.pick("Shadows", "阴影质量").into(),
+            SettingField::MasterVolume => language.pick("Master", "主音量").into(),
+            SettingField::Volume(SoundChannel::Music) => language.pick("Music", "音乐").into(),
+            SettingField::Volume(SoundChannel::Effects) => language.pick("Effects", "音效").into(),
+            SettingField::Sensitivity => language.pick("Camera Sensitivity", "视角灵敏度").into(),
+            SettingField::EdgePan => language.pick("Edge Pan", "边缘平移").into(),
+            SettingField::Language => language.pick("Language", "语言").into(),
+            SettingField::Binding(action) => match language {
+                Language::English => format!("{action:?}"),
+                Language::Chinese => action.label().into(),
+            },
+        }
+    }
+}
+
+/// 按钮的作用
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+enum SettingsButton {
+    Tab(SettingsTab),
+    /// 切换到上一个 / 下一个选项，开关类设置任一方向都是切换
+    Step(SettingField, i32),
+    Rebind(InputAction),
+    ResetBindings,
+    Close,
+}
+
+/// 显示设置项当前值的文本
+#[derive(Component, Debug, Clone, Copy)]
+struct SettingValue(SettingField);
+
+// ──────────────────────────────────────────────────────────────
+// 2. 打开与关闭
+// ──────────────────────────────────────────────────────────────
+
+/// 生成设置面板，scope 决定面板何时随状态销毁（例如 DespawnOnExit(InGameState::Paused)）
+pub fn spawn_settings_panel(commands: &mut Commands, scope: impl Bundle) {
+    commands.spawn((
+        Node {
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            position_type: PositionType::Absolute,
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        BackgroundColor(BACKDROP_COLOR),
+        FocusPolicy::Block,
+        GlobalZIndex(20),
+        SettingsPanel::default(),
+        scope,
+        Name::new("Settings"),
+    ));
+}
+
+fn open_settings_from_menu(mut commands: Commands) {
+    spawn_settings_panel(&mut commands, DespawnOnExit(MenuOptions::Setting));
+}
+
+// ──────────────────────────────────────────────────────────────
+// 3. 面板内容
+// ──────────────────────────────────────────────────────────────
+
+/// 面板刚生成、切换标签页或切换语言时重建内容
+fn rebuild_settings_panel(
+    mut commands: Commands,
+    tab: Res<SettingsTab>,
+    settings: Res<GameSettings>,
+    mut panels: Query<(Entity, &mut SettingsPanel)>,
+) {
+    let key = (*tab, settings.language);
+    for (entity, mut panel) in &mut panels {
+        if panel.built == Some(key) {
+            continue;
+        }
+        panel.built = Some(key);
+        commands
+            .entity(entity)
+            .despawn_children()
+            .with_children(|parent| build_panel(parent, *tab, settings.language));
+    }
+}
+
+fn build_panel(parent: &mut ChildSpawnerCommands, tab: SettingsTab, language: Language) {
+    parent
+        .spawn((
+            Node {
+                width: Val::Px(760.0),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Stretch,
+                padding: UiRect::all(Val::Px(28.0)),
+                row_gap: Val::Px(8.0),
+                border: UiRect::all(Val::Px(2.0)),
+                ..default()
+            },
+            BackgroundColor(PANEL_COLOR),
+            BorderRadius::all(Val::Px(24.0)),
+            BorderColor::all(ACCENT_COLOR.with_alpha(0.3)),
+        ))
+        .with_children(|panel| {
+            panel.spawn((
+                Text::new(language.pick("SETTINGS", "设置")),
+                TextFont {
+                    font_size: 36.0,
+                    ..default()
+                },
+                TextColor(ACCENT_COLOR),
+            ));
+
+            // 标签页
+            panel
+                .spawn(Node {
+                    column_gap: Val::Px(8.0),
+                    margin: UiRect::bottom(Val::Px(8.0)),
+                    ..default()
+                })
+                .with_children(|tabs| {
+                    for option in SettingsTab::ALL {
+                        let color = if option == tab {
+                            ACCENT_COLOR.with_alpha(0.6)
+                        } else {
+                            NORMAL_BUTTON
+                        };
+                        spawn_button(
+                            tabs,
+                            option.label(language),
+                            SettingsButton::Tab(option),
+                            Val::Px(160.0),
+                            color,
+                        );
+                    }
+                });
+
+            match tab {
+                SettingsTab::Graphics => {
+                    for field in [
+                        SettingField::Resolution,
+                        SettingField::WindowMode,
+                        SettingField::VSync,
+                        SettingField::Shadows,
+                    ] {
+                        spawn_step_row(panel, field, language);
+                    }
+                }
+                SettingsTab::Audio => {
+                    for field in [
+                        SettingField::MasterVolume,
+                        SettingField::Volume(SoundChannel::Music),
+                        SettingField::Volume(SoundChannel::Effects),
+                    ] {
+                        spawn_step_row(panel, field, language);
+                    }
+                }
+                SettingsTab::Controls => {
+                    spawn_step_row(panel, SettingField::Sensitivity, language);
+                    spawn_step_row(panel, SettingField::EdgePan, language);
+                    // 按键绑定分两列排列，点击后按下新按键，Esc 取消
+                    panel
+                        .spawn(Node {
+                            flex_wrap: FlexWrap::Wrap,
+                            column_gap: Val::Px(16.0),
+                            margin: UiRect::top(Val::Px(8.0)),
+                            ..default()
+                        })
+                        .with_children(|grid| {
+                            for action in InputAction::ALL {
+                                spawn_binding_row(grid, action, language);
+                            }
+                        });
+                    spawn_button(
+                        panel,
+                        language.pick("RESET BINDINGS", "恢复默认按键"),
+                        SettingsButton::ResetBindings,
+                        Val::Px(240.0),
+                        NORMAL_BUTTON,
+                    );
+                }
+                SettingsTab::Language => spawn_step_row(panel, SettingField::Language, language),
+            }
+
+            spawn_button(
+                panel,
+                language.pick("BACK", "返回"),
+                SettingsButton::Close,
+                Val::Percent(100.0),
+                NORMAL_BUTTON,
+            );
+        });
+}
+
+fn spawn_button(
+    parent: &mut ChildSpawnerCommands,
+    label: &str,
+    action: SettingsButton,
+    width: Val,
+    color: Color,
+) {
+    parent
+        .spawn((
+            Button,
+            Node {
+                width,
+                height: Val::Px(40.0),
+                margin: UiRect::top(Val::Px(8.0)),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(color),
+            BorderRadius::all(Val::Px(12.0)),
+            action,
+        ))
+        .with_child((
+            Text::new(label),
+            TextFont {
+                font_size: 20.0,
+                ..default()
+            },
+            TextColor(TEXT_COLOR),
+        ));
+}
+
+/// 名称 + [<] 当前值 [>]
+fn spawn_step_row(parent: &mut ChildSpawnerCommands, field: SettingField, language: Language) {
+    parent
+        .spawn(Node {
+            height: Val::Px(36.0),
+            align_items: AlignItems::Center,
+            column_gap: Val::Px(8.0),
+            ..default()
+        })
+        .with_children(|row| {
+            row.spawn((
+                Text::new(field.label(language)),
+                TextFont {
+                    font_size: 20.0,
+                    ..default()
+                },
+                TextColor(TEXT_COLOR),
+                Node {
+                    flex_grow: 1.0,
+                    ..default()
+                },
+            ));
+            spawn_small_button(row, "<", SettingsButton::Step(field, -1));
+            row.spawn((
+                Text::new(""),
+                TextFont {
+                    font_size: 20.0,
+                    ..default()
+                },
+                TextColor(ACCENT_COLOR),
+                TextLayout::new_with_justify(Justify::Center),
+                Node {
+                    width: Val::Px(200.0),
+                    ..default()
+                },
+                SettingValue(field),
+            ));
+            spawn_small_button(row, ">", SettingsButton::Step(field, 1));
+        });
+}
+
+/// 名称 + 当前绑定（点击改键）
+fn spawn_binding_row(parent: &mut ChildSpawnerCommands, action: InputAction, language: Language) {
+    parent
+        .spawn(Node {
+            width: Val::Px(344.0),
+            height: Val::Px(26.0),
+            align_items: AlignItems::Center,
+            ..default()
+        })
+        .with_children(|row| {
+            row.spawn((
+                Text::new(SettingField::Binding(action).label(language)),
+                TextFont {
+                    font_size: 16.0,
+                    ..default()
+                },
+                TextColor(TEXT_COLOR),
+                Node {
+                    flex_grow: 1.0,
+                    ..default()
+                },
+            ));
+            row.spawn((
+                Button,
+                Node {
+                    width: Val::Px(170.0),
+                    height: Val::Px(24.0),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                BackgroundColor(NORMAL_BUTTON),
+                BorderRadius::all(Val::Px(8.0)),
+                SettingsButton::Rebind(action),
+            ))
+            .with_child((
+                Text::new(""),
+                TextFont {
+                    font_size: 14.0,
+                    ..default()
+                },
+                TextColor(ACCENT_COLOR),
+                SettingValue(SettingField::Binding(action)),
+            ));
+        });
+}
+
+fn spawn_small_button(parent: &mut ChildSpawnerCommands, label: &str, action: SettingsButton) {
+    parent
+        .spawn((
+            Button,
+            Node {
+                width: Val::Px(36.0),
+                height: Val::Px(32.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(NORMAL_BUTTON),
+            BorderRadius::all(Val::Px(8.0)),
+            action,
+        ))
+        .with_child((
+            Text::new(label),
+            TextFont {
+                font_size: 20.0,
+                ..default()
+            },
+            TextColor(TEXT_COLOR),
+        ));
+}
+
+// ──────────────────────────────────────────────────────────────
+// 4. 交互
+// ──────────────────────────────────────────────────────────────
+
+///按钮点击逻辑
+#[allow(clippy::too_many_arguments)]
+fn settings_button_system(
+    mut commands: Commands,
+    mut interaction_query: Query<
+        (&Interaction, &SettingsButton, &mut BackgroundColor),
+        Changed<Interaction>,
+    >,
+    panels: Query<Entity, With<SettingsPanel>>,
+    app_state: Res<State<AppState>>,
+    mut tab: ResMut<SettingsTab>,
+    mut settings: ResMut<GameSettings>,
+    mut input_map: ResMut<InputMap>,
+    mut rebind: ResMut<RebindState>,
+    mut next_menu_state: ResMut<NextState<MenuOptions>>,
+) {
+    for (interaction, button, mut bg) in &mut interaction_query {
+        // 当前标签页的按钮保持高亮
+        let idle_color = match button {
+            SettingsButton::Tab(option) if *option == *tab => ACCENT_COLOR.with_alpha(0.6),
+            _ => NORMAL_BUTTON,
+        };
+        match *interaction {
+            Interaction::Pressed => *bg = PRESSED_BUTTON.into(),
+            Interaction::Hovered => {
+                *bg = HOVER_BUTTON.into();
+                continue;
+            }
+            Interaction::None => {
+                *bg = idle_color.into();
+                continue;
+            }
+        }
+
+        match *button {
+            SettingsButton::Tab(option) => {
+                *rebind = RebindState::Idle;
+                *tab = option;
+            }
+            SettingsButton::Step(field, step) => {
+                step_setting(&mut settings, field, step);
+                settings.save();
+            }
+            SettingsButton::Rebind(action) => {
+                *rebind = RebindState::Listening(action);
+            }
+            SettingsButton::ResetBindings => {
+                *rebind = RebindState::Idle;
+                *input_map = InputMap::default();
+                if let Err(error) = input_map.save(INPUT_MAP_PATH) {
+                    warn!("无法保存按键配置 {INPUT_MAP_PATH}: {error}");
+                }
+            }
+            SettingsButton::Close => {
+                *rebind = RebindState::Idle;
+                if *app_state.get() == AppState::Menu {
+                    next_menu_state.set(MenuOptions::NewGame);
+                } else {
+                    for panel in &panels {
+                        commands.entity(panel).despawn();
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// 在选项列表中前后切换，越界时循环
+fn cycle<T: Copy + PartialEq>(options: &[T], current: T, step: i32) -> T {
+    let index = options
+        .iter()
+        .position(|option| *option == current)
+        .unwrap_or(0) as i32;
+    options[(index + step).rem_euclid(options.len() as i32) as usize]
+}
+
+fn step_setting(settings: &mut GameSettings, field: SettingField, step: i32) {
+    let step_volume = |volume: &mut f32| {
+        *volume = (*volume + VOLUME_STEP * step as f32).clamp(0.0, 1.0);
+    };
+    match field {
+        SettingField::Resolution => {
+            let graphics = &mut settings.graphics;
+            graphics.resolution = cycle(&RESOLUTIONS, graphics.resolution, step);
+        }
+        SettingField::WindowMode => {
+            let graphics = &mut settings.graphics;
+            graphics.window_mode = cycle(&WindowModeSetting::ALL, graphics.window_mode, step);
+        }
+        SettingField::VSync => settings.graphics.vsync = !settings.graphics.vsync,
+        SettingField::Shadows => {
+            let graphics = &mut settings.graphics;
+            graphics.shadows = cycle(&ShadowQuality::ALL, graphics.shadows, step);
+        }
+        SettingField::MasterVolume => step_volume(&mut settings.audio.master),
+        SettingField::Volume(channel) => step_volume(settings.audio.channel_mut(channel)),
+        SettingField::Sensitivity => {
+            let controls = &mut settings.controls;
+            controls.camera_sensitivity = (controls.camera_sensitivity
+                + SENSITIVITY_STEP * step as f32)
+                .clamp(SENSITIVITY_RANGE.0, SENSITIVITY_RANGE.1);
+        }
+        SettingField::EdgePan => settings.controls.edge_pan = !settings.controls.edge_pan,
+        SettingField::Language => {
+            settings.language = cycle(&Language::ALL, settings.language, step);
+        }
+        SettingField::Binding(_) => {}
+    }
+}
+
+/// 设置、按键或改键状态变化时刷新显示的值
+fn refresh_setting_values(
+    settings: Res<GameSettings>,
+    input_map: Res<InputMap>,
+    rebind: Res<RebindState>,
+    mut values: Query<(Ref<SettingValue>, &mut Text)>,
+) {
+    let changed = settings.is_changed() || input_map.is_changed() || rebind.is_changed();
+    let language = settings.language;
+    for (value, mut text) in &mut values {
+        if !changed && !value.is_added() {
+            continue;
+        }
+        let on_off = |enabled: bool| {
+            if enabled {
+                language.pick("On", "开")
+            } else {
+                language.pick("Off", "关")
+            }
+        };
+        text.0 = match value.0 {
+            SettingField::Resolution => {
+                let (width, height) = settings.graphics.resolution;
+                format!("{width} x {height}")
+            }
+            SettingField::WindowMode => settings.graphics.window_mode.label(language).into(),
+            SettingField::VSync => on_off(settings.graphics.vsync).into(),
+            SettingField::Shadows => settings.graphics.shadows.label(language).into(),
+            SettingField::MasterVolume => format!("{:.0}%", settings.audio.master * 100.0),
+            SettingField::Volume(channel) => {
+                format!("{:.0}%", settings.audio.channel(channel) * 100.0)
+            }
+            SettingField::Sensitivity => {
+                format!("{:.1}", settings.controls.camera_sensitivity * 1000.0)
+            }
+            SettingField::EdgePan => on_off(settings.controls.edge_pan).into(),
+            SettingField::Language => settings.language.label().into(),
+            SettingField::Binding(action) if *rebind == RebindState::Listening(action) => {
+                language.pick("Press a key...", "请按键…").into()
+            }
+            SettingField::Binding(action) => binding_text(&input_map, action),
+        };
+    }
+}
+
+/// 键鼠绑定的简短描述，手柄绑定不在这里显示
+fn binding_text(input_map: &InputMap, action: InputAction) -> String {
+    let labels: Vec<String> = input_map
+        .bindings(action)
+        .iter()
+        .filter(|binding| !binding.is_gamepad())
+        .map(|binding| match binding {
+            InputBinding::Key(key) => format!("{key:?}"),
+            InputBinding::Mouse(button) => format!("Mouse {button:?}"),
+            InputBinding::MouseWheel(direction) => format!("Wheel {direction:?}"),
+            _ => String::new(),
+        })
+        .collect();
+    if labels.is_empty() {
+        "-".into()
+    } else {
+        labels.join(", ")
+    }
+}
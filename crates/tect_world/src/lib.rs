@@ -1 +1,4 @@
-pub mod world_map;
\ No newline at end of file
+pub mod save;
+pub mod save_format;
+pub mod unit_data;
+pub mod world_map;
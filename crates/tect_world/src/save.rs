@@ -0,0 +1,258 @@
+///存档：把单位、相机与场景物件保存到 saves/<槽位>.sav，文件格式与版本迁移见 save_format
+///收到 SaveGame 消息时保存，游戏中按固定间隔自动存档；Loading 阶段 world_map 根据 PendingLoad 读取存档，进入 InGame 时重建世界
+///启动时读取各槽位的格式头填写 SaveSlots，保存成功后更新对应槽位，供存档列表显示
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use tect_camera::bookmarks::CameraBookmarks;
+use tect_camera::god_view_camera::{CameraPose, GodViewCamera};
+use tect_control::moving::PlayerMove;
+use tect_control::orders::OrderQueue;
+use tect_control::selection::Selected;
+use tect_state::app_state::*;
+use tect_state::save::{SaveGame, SaveSlot, SaveSlotInfo, SaveSlots};
+
+use crate::save_format::{
+    read_save_file, read_save_header, write_save_file, SaveError, SaveHeader, SaveMigrations,
+};
+use crate::world_map::{UnitKind, WorldObject};
+
+/// 存档目录
+pub const SAVE_DIR: &str = "saves";
+/// 自动存档间隔（秒）
+const AUTOSAVE_INTERVAL: f32 = 300.0;
+
+pub struct SavePlugin;
+
+impl Plugin for SavePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SaveMigrations>()
+            .insert_resource(AutosaveTimer(Timer::from_seconds(
+                AUTOSAVE_INTERVAL,
+                TimerMode::Repeating,
+            )))
+            .add_systems(Startup, scan_save_slots)
+            .add_systems(OnEnter(AppState::InGame), reset_autosave_timer)
+            .add_systems(
+                Update,
+                (autosave, save_game_system)
+                    .run_if(in_state(AppState::InGame))
+                    .chain(),
+            );
+    }
+}
+
+// ──────────────────────────────────────────────────────────────
+// 1. 存档内容
+// ──────────────────────────────────────────────────────────────
+
+/// 一份完整的存档
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SaveData {
+    /// 地图 id，对应 scnens/<id>.glb
+    pub map: String,
+    pub camera: CameraPose,
+    pub bookmarks: CameraBookmarks,
+    pub units: Vec<SavedUnit>,
+    pub objects: Vec<SavedObject>,
+}
+
+/// 单位
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SavedUnit {
+    pub kind: UnitKind,
+    pub transform: Transform,
+    pub move_speed: f32,
+    /// 正在前往的目标与剩余路径点
+    pub target_position: Option<Vec3>,
+    pub waypoints: Vec<Vec3>,
+    /// 排队中的移动、攻击移动与巡逻命令
+    #[serde(default)]
+    pub orders: OrderQueue,
+    pub selected: bool,
+}
+
+/// 场景物件
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SavedObject {
+    pub object: WorldObject,
+    pub transform: Transform,
+}
+
+/// 槽位对应的存档文件
+pub fn save_path(slot: SaveSlot) -> PathBuf {
+    PathBuf::from(SAVE_DIR).join(format!("{slot}.sav"))
+}
+
+/// 写入存档，返回写入的格式头
+pub fn write_save(slot: SaveSlot, data: &SaveData) -> Result<SaveHeader, SaveError> {
+    let header = SaveHeader::new(data.map.clone());
+    write_save_file(&save_path(slot), header.clone(), data)?;
+    Ok(header)
+}
+
+/// 读取存档，旧版本存档按迁移表升级
+pub fn read_save(slot: SaveSlot, migrations: &SaveMigrations) -> Result<SaveData, SaveError> {
+    read_save_file(&save_path(slot), migrations).map(|(_, data)| data)
+}
+
+fn slot_info(header: SaveHeader) -> SaveSlotInfo {
+    SaveSlotInfo {
+        map: header.map,
+        saved_at: header.saved_at,
+    }
+}
+
+/// 读取已有存档的格式头；损坏或版本过新的存档不显示
+fn scan_save_slots(mut slots: ResMut<SaveSlots>) {
+    slots.0.clear();
+    for slot in SaveSlot::all() {
+        let path = save_path(slot);
+        if !path.exists() {
+            continue;
+        }
+        match read_save_header(&path) {
+            Ok(header) => {
+                slots.0.insert(slot, slot_info(header));
+            }
+            Err(error) => warn!("无法读取存档 {}: {error}", path.display()),
+        }
+    }
+}
+
+// ──────────────────────────────────────────────────────────────
+// 2. 保存与自动存档
+// ──────────────────────────────────────────────────────────────
+
+#[derive(Resource)]
+struct AutosaveTimer(Timer);
+
+fn reset_autosave_timer(mut timer: ResMut<AutosaveTimer>) {
+    timer.0.reset();
+}
+
+fn autosave(time: Res<Time>, mut timer: ResMut<AutosaveTimer>, mut saves: MessageWriter<SaveGame>) {
+    if timer.0.tick(time.delta()).just_finished() {
+        saves.write(SaveGame(SaveSlot::Auto));
+    }
+}
+
+fn save_game_system(
+    mut requests: MessageReader<SaveGame>,
+    mut slots: ResMut<SaveSlots>,
+    map: Option<Res<CurrentMap>>,
+    bookmarks: Res<CameraBookmarks>,
+    camera: Query<&GodViewCamera>,
+    units: Query<(
+        &UnitKind,
+        &Transform,
+        &PlayerMove,
+        &OrderQueue,
+        Has<Selected>,
+    )>,
+    objects: Query<(&WorldObject, &Transform)>,
+) {
+    for SaveGame(slot) in requests.read() {
+        let (Some(map), Ok(camera)) = (map.as_ref(), camera.single()) else {
+            warn!("当前没有可保存的游戏");
+            continue;
+        };
+        let data = SaveData {
+            map: map.id.clone(),
+            camera: camera.target(),
+            bookmarks: bookmarks.clone(),
+            units: units
+                .iter()
+                .map(|(kind, transform, player, orders, selected)| SavedUnit {
+                    kind: *kind,
+                    transform: *transform,
+                    move_speed: player.move_speed,
+                    target_position: player.target_position,
+                    waypoints: player.waypoints.iter().copied().collect(),
+                    orders: orders.clone(),
+                    selected,
+                })
+                .collect(),
+            objects: objects
+                .iter()
+                .map(|(object, transform)| SavedObject {
+                    object: object.clone(),
+                    transform: *transform,
+                })
+                .collect(),
+        };
+        match write_save(*slot, &data) {
+            Ok(header) => {
+                info!("已保存到 {}", save_path(*slot).display());
+                slots.0.insert(*slot, slot_info(header));
+            }
+            Err(error) => warn!("保存 {} 失败: {error}", save_path(*slot).display()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tect_control::orders::Order;
+
+    fn sample_save() -> SaveData {
+        let camera = CameraPose {
+            focus: Vec3::new(3.0, 0.0, -2.0),
+            distance: 18.0,
+            yaw: 0.7,
+            pitch: -0.9,
+        };
+        let mut bookmarks = CameraBookmarks::default();
+        bookmarks.set(2, camera);
+
+        let mut orders = OrderQueue::default();
+        orders.replace(Order::Move(Vec3::new(4.0, 0.0, 4.0)));
+        orders.push(Order::AttackMove(Vec3::new(8.0, 0.0, 0.0)));
+        orders.push(Order::patrol(Vec3::new(-6.0, 0.0, 1.0)));
+
+        SaveData {
+            map: "simple_map".into(),
+            camera,
+            bookmarks,
+            units: vec![SavedUnit {
+                kind: UnitKind::Rola,
+                transform: Transform::from_xyz(1.0, 1.0, 2.0)
+                    .with_rotation(Quat::from_rotation_y(1.2)),
+                move_speed: 4.0,
+                target_position: Some(Vec3::new(4.0, 0.0, 4.0)),
+                waypoints: vec![Vec3::new(2.0, 0.0, 3.0), Vec3::new(4.0, 0.0, 4.0)],
+                orders,
+                selected: true,
+            }],
+            objects: vec![SavedObject {
+                object: WorldObject {
+                    scene: "scnens/robot_01.glb".into(),
+                },
+                transform: Transform::from_xyz(-4.0, 0.0, 3.0),
+            }],
+        }
+    }
+
+    #[test]
+    fn save_data_round_trips_through_file() {
+        let path = std::env::temp_dir().join(format!("tect_save_{}_data.sav", std::process::id()));
+        let save = sample_save();
+        write_save_file(&path, SaveHeader::new(save.map.clone()), &save).unwrap();
+
+        let read = read_save_file::<SaveData>(&path, &SaveMigrations::default());
+        let _ = std::fs::remove_file(&path);
+        let (header, read) = read.unwrap();
+        assert_eq!(header.map, "simple_map");
+        assert_eq!(read, save);
+    }
+
+    #[test]
+    fn units_without_orders_load_with_empty_queue() {
+        let mut value = serde_json::to_value(sample_save()).unwrap();
+        value["units"][0].as_object_mut().unwrap().remove("orders");
+
+        let read: SaveData = serde_json::from_value(value).unwrap();
+        assert!(read.units[0].orders.is_empty());
+    }
+}
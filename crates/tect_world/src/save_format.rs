@@ -0,0 +1,322 @@
+///存档文件格式：gzip 压缩的 JSON，由格式头（格式标识、版本号、地图等）与存档内容两部分组成
+///读取旧版本存档时，先按迁移表逐个版本升级 JSON 内容，再反序列化为当前的 SaveData；损坏或版本过新的文件返回 SaveError
+use bevy::prelude::*;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde::{
+    de::{DeserializeOwned, IgnoredAny},
+    Deserialize, Serialize,
+};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::io::{Read, Write};
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+use thiserror::Error;
+
+/// 格式标识，用于识别不是本游戏存档的文件
+pub const SAVE_FORMAT: &str = "tect-save";
+/// 当前存档格式版本，存档内容的结构变化时加 1 并注册对应的迁移
+pub const SAVE_VERSION: u32 = 1;
+
+/// 存档读写错误
+#[derive(Debug, Error)]
+pub enum SaveError {
+    #[error("存档读写失败: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("存档已损坏: {0}")]
+    Corrupt(String),
+    #[error("存档版本 {found} 高于当前支持的版本 {supported}，请更新游戏")]
+    TooNew { found: u32, supported: u32 },
+    #[error("缺少从版本 {0} 升级的迁移")]
+    MissingMigration(u32),
+    #[error("存档从版本 {from} 升级失败: {reason}")]
+    Migration { from: u32, reason: String },
+}
+
+/// 格式头，可以跳过存档内容单独读取（例如在存档列表中显示）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SaveHeader {
+    pub format: String,
+    pub version: u32,
+    /// 地图 id
+    pub map: String,
+    /// 保存时间（Unix 时间戳，秒）
+    pub saved_at: u64,
+}
+
+impl SaveHeader {
+    pub fn new(map: impl Into<String>) -> Self {
+        Self {
+            format: SAVE_FORMAT.to_string(),
+            version: SAVE_VERSION,
+            map: map.into(),
+            saved_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |duration| duration.as_secs()),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct SaveFile<T> {
+    header: SaveHeader,
+    payload: T,
+}
+
+/// 把 `from` 版本的存档内容原地升级到 `from + 1` 版本
+pub type SaveMigration = fn(&mut Value) -> Result<(), String>;
+
+/// 存档迁移表：按起始版本登记升级函数，读取旧存档时依次执行
+#[derive(Resource, Default)]
+pub struct SaveMigrations {
+    steps: BTreeMap<u32, SaveMigration>,
+}
+
+impl SaveMigrations {
+    /// 登记从 `from` 升级到 `from + 1` 的迁移
+    pub fn register(&mut self, from: u32, migration: SaveMigration) -> &mut Self {
+        self.steps.insert(from, migration);
+        self
+    }
+
+    /// 把 `version` 版本的内容升级到当前版本
+    pub fn migrate(&self, payload: &mut Value, version: u32) -> Result<(), SaveError> {
+        self.migrate_to(payload, version, SAVE_VERSION)
+    }
+
+    fn migrate_to(&self, payload: &mut Value, version: u32, target: u32) -> Result<(), SaveError> {
+        for from in version..target {
+            let migration = self
+                .steps
+                .get(&from)
+                .ok_or(SaveError::MissingMigration(from))?;
+            migration(payload).map_err(|reason| SaveError::Migration { from, reason })?;
+        }
+        Ok(())
+    }
+}
+
+/// 压缩写入存档；先写临时文件再替换，写到一半退出不会损坏原有存档
+pub fn write_save_file<T: Serialize>(
+    path: &Path,
+    header: SaveHeader,
+    payload: &T,
+) -> Result<(), SaveError> {
+    let json = serde_json::to_vec(&SaveFile { header, payload }).map_err(std::io::Error::other)?;
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(&json)?;
+    let bytes = encoder.finish()?;
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let temp = path.with_extension("tmp");
+    std::fs::write(&temp, bytes)?;
+    std::fs::rename(temp, path)?;
+    Ok(())
+}
+
+/// 读取存档，旧版本的内容按迁移表升级
+pub fn read_save_file<T: DeserializeOwned>(
+    path: &Path,
+    migrations: &SaveMigrations,
+) -> Result<(SaveHeader, T), SaveError> {
+    let SaveFile {
+        header,
+        mut payload,
+    } = read_raw(path)?;
+    migrations.migrate(&mut payload, header.version)?;
+    let payload =
+        serde_json::from_value(payload).map_err(|error| SaveError::Corrupt(error.to_string()))?;
+    Ok((header, payload))
+}
+
+/// 只读取格式头：存档内容仍需解压，但只做语法检查、不构建 JSON 树
+pub fn read_save_header(path: &Path) -> Result<SaveHeader, SaveError> {
+    read_raw::<IgnoredAny>(path).map(|file| file.header)
+}
+
+fn read_raw<T: DeserializeOwned>(path: &Path) -> Result<SaveFile<T>, SaveError> {
+    let bytes = std::fs::read(path)?;
+    let mut json = Vec::new();
+    GzDecoder::new(bytes.as_slice())
+        .read_to_end(&mut json)
+        .map_err(|error| SaveError::Corrupt(format!("解压失败: {error}")))?;
+    let file: SaveFile<T> =
+        serde_json::from_slice(&json).map_err(|error| SaveError::Corrupt(error.to_string()))?;
+
+    if file.header.format != SAVE_FORMAT {
+        return Err(SaveError::Corrupt(format!(
+            "未知的存档格式 {}",
+            file.header.format
+        )));
+    }
+    if file.header.version > SAVE_VERSION {
+        return Err(SaveError::TooNew {
+            found: file.header.version,
+            supported: SAVE_VERSION,
+        });
+    }
+    Ok(file)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::path::PathBuf;
+
+    /// 每个测试使用独立的临时文件，结束时删除
+    struct TempSave(PathBuf);
+
+    impl TempSave {
+        fn new(name: &str) -> Self {
+            let file = format!("tect_save_{}_{name}.sav", std::process::id());
+            Self(std::env::temp_dir().join(file))
+        }
+    }
+
+    impl Drop for TempSave {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_file(&self.0);
+        }
+    }
+
+    fn header(version: u32) -> SaveHeader {
+        SaveHeader {
+            version,
+            ..SaveHeader::new("test_map")
+        }
+    }
+
+    fn rename_units(payload: &mut Value) -> Result<(), String> {
+        let units = payload
+            .as_object_mut()
+            .and_then(|object| object.remove("unit_list"))
+            .ok_or("缺少 unit_list")?;
+        payload["units"] = units;
+        Ok(())
+    }
+
+    fn add_objects(payload: &mut Value) -> Result<(), String> {
+        payload["objects"] = json!([]);
+        Ok(())
+    }
+
+    fn count_units(payload: &mut Value) -> Result<(), String> {
+        let count = payload["units"].as_array().map_or(0, Vec::len);
+        payload["unit_count"] = json!(count);
+        Ok(())
+    }
+
+    #[test]
+    fn migrate_runs_steps_in_order() {
+        let mut migrations = SaveMigrations::default();
+        migrations
+            .register(2, count_units)
+            .register(0, rename_units)
+            .register(1, add_objects);
+
+        let mut payload = json!({ "unit_list": [1, 2, 3] });
+        migrations.migrate_to(&mut payload, 0, 3).unwrap();
+        assert_eq!(
+            payload,
+            json!({ "units": [1, 2, 3], "objects": [], "unit_count": 3 })
+        );
+
+        // 从中间版本开始只执行剩余的迁移
+        let mut payload = json!({ "units": [1] });
+        migrations.migrate_to(&mut payload, 1, 3).unwrap();
+        assert_eq!(
+            payload,
+            json!({ "units": [1], "objects": [], "unit_count": 1 })
+        );
+    }
+
+    #[test]
+    fn migrate_reports_missing_step() {
+        let mut migrations = SaveMigrations::default();
+        migrations
+            .register(0, rename_units)
+            .register(2, count_units);
+
+        let mut payload = json!({ "unit_list": [] });
+        let error = migrations.migrate_to(&mut payload, 0, 3).unwrap_err();
+        assert!(matches!(error, SaveError::MissingMigration(1)), "{error}");
+    }
+
+    #[test]
+    fn migrate_reports_failed_step() {
+        let mut migrations = SaveMigrations::default();
+        migrations.register(0, rename_units);
+
+        let mut payload = json!({});
+        let error = migrations.migrate_to(&mut payload, 0, 1).unwrap_err();
+        assert!(
+            matches!(error, SaveError::Migration { from: 0, .. }),
+            "{error}"
+        );
+    }
+
+    #[test]
+    fn round_trip_current_version() {
+        let save = TempSave::new("round_trip");
+        let payload = json!({ "units": [1, 2] });
+        write_save_file(&save.0, header(SAVE_VERSION), &payload).unwrap();
+
+        let (read_header, read_payload): (SaveHeader, Value) =
+            read_save_file(&save.0, &SaveMigrations::default()).unwrap();
+        assert_eq!(read_header.map, "test_map");
+        assert_eq!(read_payload, payload);
+        assert_eq!(read_save_header(&save.0).unwrap(), read_header);
+    }
+
+    #[test]
+    fn old_version_without_migration_fails() {
+        let save = TempSave::new("missing_migration");
+        write_save_file(&save.0, header(SAVE_VERSION - 1), &json!({})).unwrap();
+
+        let error = read_save_file::<Value>(&save.0, &SaveMigrations::default()).unwrap_err();
+        assert!(
+            matches!(error, SaveError::MissingMigration(from) if from == SAVE_VERSION - 1),
+            "{error}"
+        );
+    }
+
+    #[test]
+    fn newer_version_is_rejected() {
+        let save = TempSave::new("too_new");
+        write_save_file(&save.0, header(SAVE_VERSION + 1), &json!({})).unwrap();
+
+        let error = read_save_header(&save.0).unwrap_err();
+        assert!(
+            matches!(error, SaveError::TooNew { found, supported }
+                if found == SAVE_VERSION + 1 && supported == SAVE_VERSION),
+            "{error}"
+        );
+    }
+
+    #[test]
+    fn wrong_format_tag_is_corrupt() {
+        let save = TempSave::new("wrong_format");
+        let header = SaveHeader {
+            format: "other-game".into(),
+            ..header(SAVE_VERSION)
+        };
+        write_save_file(&save.0, header, &json!({})).unwrap();
+
+        let error = read_save_header(&save.0).unwrap_err();
+        assert!(matches!(error, SaveError::Corrupt(_)), "{error}");
+    }
+
+    #[test]
+    fn invalid_gzip_is_corrupt() {
+        let save = TempSave::new("invalid_gzip");
+        std::fs::write(&save.0, b"not a gzip stream").unwrap();
+
+        let error = read_save_file::<Value>(&save.0, &SaveMigrations::default()).unwrap_err();
+        assert!(matches!(error, SaveError::Corrupt(_)), "{error}");
+    }
+}
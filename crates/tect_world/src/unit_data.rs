@@ -0,0 +1,110 @@
+///单位数据：每种单位的模型、移动速度与动画配置写在 assets/units/<id>.unit.ron 中，例如：
+///(
+///    scene: "rola/rola_walk.glb",
+///    move_speed: 4.0,
+///    height_offset: 1.0,
+///    animations: (
+///        walk: Some((path: "rola/rola_walk.glb", index: 0, authored_speed: 2.0)),
+///        run: Some((path: "rola/rola_run.glb", index: 0, authored_speed: 4.0)),
+///        run_speed: 3.0,
+///    ),
+///)
+///加载单位数据时会一并加载其引用的 glTF，登记到 LoadingAssets 后加载界面会等待它们全部就绪
+use bevy::{
+    asset::{io::Reader, AssetLoader, LoadContext},
+    gltf::Gltf,
+    prelude::*,
+};
+use serde::Deserialize;
+use tect_control::animation::CharacterAnimationSet;
+
+pub struct UnitDataPlugin;
+
+impl Plugin for UnitDataPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<UnitDefinition>()
+            .init_asset_loader::<UnitDefinitionLoader>();
+    }
+}
+
+/// 一种单位的数据
+#[derive(Asset, TypePath, Debug, Clone, Deserialize)]
+pub struct UnitDefinition {
+    /// 单位模型所在的 glTF 文件，使用其中第一个场景
+    pub scene: String,
+    /// 最大移动速度（米/秒），对应 PlayerMove::move_speed
+    pub move_speed: f32,
+    /// 模型原点在脚底上方的高度，对应 TerrainFollow::height_offset
+    #[serde(default)]
+    pub height_offset: f32,
+    /// 待机 / 行走 / 奔跑动画，切换阈值应在 move_speed 以内
+    #[serde(default)]
+    pub animations: CharacterAnimationSet,
+    /// 引用的 glTF，作为依赖随单位数据一起加载并保持加载状态
+    #[serde(skip)]
+    gltfs: Vec<Handle<Gltf>>,
+}
+
+#[derive(Default, TypePath)]
+struct UnitDefinitionLoader;
+
+impl AssetLoader for UnitDefinitionLoader {
+    type Asset = UnitDefinition;
+    type Settings = ();
+    type Error = BevyError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        load_context: &mut LoadContext<'_>,
+    ) -> Result<UnitDefinition, BevyError> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let mut definition: UnitDefinition = ron::de::from_bytes(&bytes)?;
+
+        let animations = &definition.animations;
+        if animations.run.is_some() && animations.run_speed > definition.move_speed {
+            warn!(
+                "{}: 奔跑阈值 {} 超过最大移动速度 {}，单位永远不会切换到奔跑动画",
+                load_context.path().display(),
+                animations.run_speed,
+                definition.move_speed
+            );
+        }
+
+        let mut paths = vec![definition.scene.clone()];
+        paths.extend(
+            [&animations.idle, &animations.walk, &animations.run]
+                .into_iter()
+                .flatten()
+                .map(|clip| clip.path.clone()),
+        );
+        paths.sort();
+        paths.dedup();
+        definition.gltfs = paths
+            .into_iter()
+            .map(|path| load_context.load(path))
+            .collect();
+        Ok(definition)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["unit.ron"]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rola_definition_parses_and_can_run() {
+        let definition: UnitDefinition =
+            ron::from_str(include_str!("../../../assets/units/rola.unit.ron")).unwrap();
+        let animations = &definition.animations;
+        assert!(animations.walk.is_some() && animations.run.is_some());
+        assert!(animations.idle_speed < animations.run_speed);
+        assert!(animations.run_speed <= definition.move_speed);
+    }
+}
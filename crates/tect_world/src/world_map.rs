@@ -1,26 +1,157 @@
 use bevy::color::palettes::css::*;
+use bevy::gltf::Gltf;
 use bevy::prelude::*;
-use std::f32::consts::PI;
-use tect_camera::god_view_camera::{calculate_rotation, GodViewCamera, GodViewCameraPlugin};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tect_camera::bookmarks::CameraBookmarks;
+use tect_camera::god_view_camera::{GodViewCamera, GodViewCameraPlugin};
 use tect_control::moving::{Ground, MoveControlPlugin, PlayerMove};
+use tect_control::selection::Selected;
+use tect_control::terrain::TerrainFollow;
 use tect_state::app_state::*;
+use tect_state::loading::LoadingAssets;
+use tect_state::save::PendingLoad;
+use tect_systems::physics::PhysicsPlugin;
+
+use crate::save::{read_save, save_path, SaveData, SavePlugin};
+use crate::save_format::SaveMigrations;
+use crate::unit_data::{UnitDataPlugin, UnitDefinition};
 
 pub struct WorldScenePlugin;
 
 impl Plugin for WorldScenePlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins((MoveControlPlugin, GodViewCameraPlugin))
-            .add_systems(OnEnter(AppState::InGame), setup);
+        app.add_plugins((
+            MoveControlPlugin,
+            GodViewCameraPlugin,
+            PhysicsPlugin,
+            SavePlugin,
+            UnitDataPlugin,
+        ))
+        .init_resource::<WorldToSpawn>()
+        .init_resource::<UnitDefinitions>()
+        .add_systems(OnEnter(AppState::Loading), prepare_world)
+        .add_systems(OnEnter(AppState::InGame), setup)
+        .add_systems(OnExit(AppState::InGame), teardown);
+    }
+}
+
+// 新游戏使用的地图
+const DEFAULT_MAP: &str = "simple_map";
+// 新游戏摆放的场景物件：glTF 路径与位置
+const DEFAULT_OBJECTS: [(&str, Vec3); 1] = [("scnens/robot_01.glb", Vec3::new(-4.0, 0.0, 3.0))];
+
+/// 单位种类，决定生成时使用的单位数据，存档中据此重建单位
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum UnitKind {
+    Rola,
+}
+
+impl UnitKind {
+    /// 单位数据文件，进入游戏前连同其引用的模型与动画一起预加载
+    pub fn definition_path(self) -> &'static str {
+        match self {
+            UnitKind::Rola => "units/rola.unit.ron",
+        }
+    }
+}
+
+/// 可存档的场景物件：由 spawn_world_object 生成，保存时记录位置，读档时按 scene 重新加载 glTF 场景
+#[derive(Component, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WorldObject {
+    /// glTF 资源路径
+    pub scene: String,
+}
+
+/// Loading 阶段读取的存档，进入 InGame 时据此生成世界；为 None 时开始新游戏
+#[derive(Resource, Debug, Default)]
+struct WorldToSpawn(Option<SaveData>);
+
+/// Loading 阶段加载的单位数据，生成单位时按种类读取
+#[derive(Resource, Debug, Default)]
+struct UnitDefinitions(HashMap<UnitKind, Handle<UnitDefinition>>);
+
+impl UnitDefinitions {
+    fn get<'a>(
+        &self,
+        assets: &'a Assets<UnitDefinition>,
+        kind: UnitKind,
+    ) -> Option<&'a UnitDefinition> {
+        let definition = self.0.get(&kind).and_then(|handle| assets.get(handle));
+        if definition.is_none() {
+            warn!("单位数据 {} 未加载，跳过生成", kind.definition_path());
+        }
+        definition
     }
 }
 
-// 初始化测试系统
+// 进入 Loading：读取待载入的存档，登记地图、单位与场景物件的资源
+fn prepare_world(
+    asset_server: Res<AssetServer>,
+    mut pending_load: ResMut<PendingLoad>,
+    migrations: Res<SaveMigrations>,
+    mut world: ResMut<WorldToSpawn>,
+    mut units: ResMut<UnitDefinitions>,
+    mut loading: ResMut<LoadingAssets>,
+) {
+    let slot = match std::mem::take(&mut *pending_load) {
+        PendingLoad::NewGame => None,
+        PendingLoad::Slot(slot) => Some(slot),
+    };
+    let save = slot.and_then(|slot| match read_save(slot, &migrations) {
+        Ok(save) => Some(save),
+        Err(error) => {
+            warn!(
+                "读取存档 {} 失败，开始新游戏: {error}",
+                save_path(slot).display()
+            );
+            None
+        }
+    });
+
+    let mut paths = Vec::new();
+    let mut kinds = Vec::new();
+    match &save {
+        Some(save) => {
+            paths.push(map_path(&save.map));
+            kinds.extend(save.units.iter().map(|unit| unit.kind));
+            paths.extend(save.objects.iter().map(|saved| saved.object.scene.clone()));
+        }
+        None => {
+            paths.push(map_path(DEFAULT_MAP));
+            kinds.push(UnitKind::Rola);
+            paths.extend(DEFAULT_OBJECTS.iter().map(|(scene, _)| scene.to_string()));
+        }
+    }
+    paths.sort();
+    paths.dedup();
+    for path in paths {
+        let handle = asset_server.load::<Gltf>(path.clone());
+        loading.add(path, handle);
+    }
+
+    units.0.clear();
+    for kind in kinds {
+        units.0.entry(kind).or_insert_with(|| {
+            let handle = asset_server.load::<UnitDefinition>(kind.definition_path());
+            loading.add(kind.definition_path(), handle.clone());
+            handle
+        });
+    }
+
+    world.0 = save;
+}
+
+// 初始化测试系统：有待读取的存档时按存档重建世界，否则开始新游戏
 fn setup(
     mut commands: Commands,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<StandardMaterial>>,
     asset_server: Res<AssetServer>,
+    mut world: ResMut<WorldToSpawn>,
+    units: Res<UnitDefinitions>,
+    definitions: Res<Assets<UnitDefinition>>,
 ) {
+    let save = world.0.take();
+
     //点光源
     // commands.spawn((
     //     PointLight {
@@ -40,11 +171,15 @@ fn setup(
     //     )],
     // ));
 
-    let camera_data = GodViewCamera::default();
+    let mut camera_data = GodViewCamera::default();
+    if let Some(save) = &save {
+        camera_data.set_target(save.camera);
+        camera_data.snap();
+    }
 
-    // 初始化时，根据默认 Yaw 和 Pitch 计算 Transform
-    let rotation = calculate_rotation(0.0, camera_data.default_pitch);
-    let translation = camera_data.focus + rotation * Vec3::new(0.0, 0.0, camera_data.distance);
+    // 初始化时，根据当前姿态计算 Transform
+    let rotation = camera_data.current.rotation();
+    let translation = camera_data.current.translation();
     // camera
     commands.spawn((
         // Camera3d::default(),
@@ -62,25 +197,141 @@ fn setup(
             ..default()
         },
         camera_data,
+        DespawnOnExit(AppState::InGame),
     ));
 
-    // 角色
+    match save {
+        Some(save) => spawn_saved_world(&mut commands, &asset_server, &units, &definitions, save),
+        None => {
+            // 新游戏：从空书签开始
+            commands.insert_resource(CameraBookmarks::default());
+            spawn_map(&mut commands, &asset_server, DEFAULT_MAP);
+            for (scene, position) in DEFAULT_OBJECTS {
+                spawn_world_object(
+                    &mut commands,
+                    &asset_server,
+                    WorldObject {
+                        scene: scene.to_string(),
+                    },
+                    Transform::from_translation(position),
+                );
+            }
+            // 初始即选中，右键可直接下达移动命令
+            if let Some(definition) = units.get(&definitions, UnitKind::Rola) {
+                spawn_unit(
+                    &mut commands,
+                    &asset_server,
+                    UnitKind::Rola,
+                    definition,
+                    Transform::from_xyz(5.0, 1.0, 2.0),
+                )
+                .insert(Selected);
+            }
+        }
+    }
+}
+
+/// 回到主菜单：setup 生成的实体带有 DespawnOnExit 会自动销毁，这里移除当前地图；
+/// 其余按局区分的状态由各自的插件在 OnExit(AppState::InGame) 中清理：
+/// 导航网格（NavMeshPlugin）、物理世界（PhysicsPlugin）、相机边界 / 跟随 / 书签（GodViewCameraPlugin）
+fn teardown(mut commands: Commands) {
+    commands.remove_resource::<CurrentMap>();
+}
+
+/// 按存档内容生成地图、单位与场景物件
+fn spawn_saved_world(
+    commands: &mut Commands,
+    asset_server: &AssetServer,
+    units: &UnitDefinitions,
+    definitions: &Assets<UnitDefinition>,
+    save: SaveData,
+) {
+    spawn_map(commands, asset_server, &save.map);
+    commands.insert_resource(save.bookmarks);
+
+    for unit in save.units {
+        let Some(definition) = units.get(definitions, unit.kind) else {
+            continue;
+        };
+        let mut entity = spawn_unit(
+            commands,
+            asset_server,
+            unit.kind,
+            definition,
+            unit.transform,
+        );
+        entity.insert((
+            PlayerMove {
+                move_speed: unit.move_speed,
+                target_position: unit.target_position,
+                waypoints: unit.waypoints.into(),
+                ..default()
+            },
+            unit.orders,
+        ));
+        if unit.selected {
+            entity.insert(Selected);
+        }
+    }
+
+    for saved in save.objects {
+        spawn_world_object(commands, asset_server, saved.object, saved.transform);
+    }
+}
+
+/// 可存档的场景物件
+fn spawn_world_object(
+    commands: &mut Commands,
+    asset_server: &AssetServer,
+    object: WorldObject,
+    transform: Transform,
+) {
     commands.spawn((
-        SceneRoot(asset_server.load(GltfAssetLabel::Scene(0).from_asset("rola/rola_walk.glb"))),
-        Transform {
-            translation: Vec3::new(5.0, 1.0, 2.0),
-            ..default()
-        },
-        PlayerMove {
-            move_speed: 2.0,
-            target_position: None,
-        },
+        SceneRoot(asset_server.load(GltfAssetLabel::Scene(0).from_asset(object.scene.clone()))),
+        transform,
+        object,
+        DespawnOnExit(AppState::InGame),
     ));
+}
 
-    // 场景
+/// 地图 id 对应的场景文件
+fn map_path(map: &str) -> String {
+    format!("scnens/{map}.glb")
+}
+
+/// 地图场景，id 对应 scnens/<id>.glb
+fn spawn_map(commands: &mut Commands, asset_server: &AssetServer, map: &str) {
+    commands.insert_resource(CurrentMap::new(map));
     commands.spawn((
-        SceneRoot(asset_server.load(GltfAssetLabel::Scene(0).from_asset("scnens/simple_map.glb"))),
+        SceneRoot(asset_server.load(GltfAssetLabel::Scene(0).from_asset(map_path(map)))),
         Transform::from_scale(Vec3::splat(1.0)),
         Ground,
+        DespawnOnExit(AppState::InGame),
     ));
 }
+
+/// 按单位数据生成单位
+pub fn spawn_unit<'a>(
+    commands: &'a mut Commands,
+    asset_server: &AssetServer,
+    kind: UnitKind,
+    definition: &UnitDefinition,
+    transform: Transform,
+) -> EntityCommands<'a> {
+    commands.spawn((
+        SceneRoot(asset_server.load(GltfAssetLabel::Scene(0).from_asset(definition.scene.clone()))),
+        transform,
+        PlayerMove {
+            move_speed: definition.move_speed,
+            ..default()
+        },
+        TerrainFollow {
+            height_offset: definition.height_offset,
+            ..default()
+        },
+        // 待机 / 行走 / 奔跑动画随移动速度切换
+        definition.animations.clone(),
+        kind,
+        DespawnOnExit(AppState::InGame),
+    ))
+}
@@ -1,5 +1,3 @@
-use tect_app;
-
 //游戏入口
 fn main() {
     tect_app::run();